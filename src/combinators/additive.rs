@@ -0,0 +1,58 @@
+use crate::{Animatable, Animation, BoundedAnimation};
+use std::{marker::PhantomData, time::Duration};
+
+/// See [`Animation::additive`] for details.
+#[derive(Debug)]
+pub struct Additive<A, B, V>
+where
+    A: Animation<V>,
+    B: Animation<V>,
+    V: Animatable,
+{
+    base: A,
+    overlay: B,
+    // The overlay's first sample, if the overlay is relative
+    origin: Option<V>,
+    _marker: PhantomData<V>,
+}
+
+impl<A, B, V> Animation<V> for Additive<A, B, V>
+where
+    A: Animation<V>,
+    B: Animation<V>,
+    V: Animatable,
+{
+    fn sample(&self, elapsed: Duration) -> V {
+        let delta = self.overlay.sample(elapsed);
+        let delta = self.origin.map(|origin| delta.sub(origin)).unwrap_or(delta);
+        self.base.sample(elapsed).add(delta)
+    }
+}
+
+impl<A, B, V> BoundedAnimation<V> for Additive<A, B, V>
+where
+    A: BoundedAnimation<V>,
+    B: BoundedAnimation<V>,
+    V: Animatable,
+{
+    fn duration(&self) -> Duration {
+        self.base.duration().max(self.overlay.duration())
+    }
+}
+
+impl<A, B, V> Additive<A, B, V>
+where
+    A: Animation<V>,
+    B: Animation<V>,
+    V: Animatable,
+{
+    pub(crate) fn new(base: A, overlay: B, relative: bool) -> Self {
+        let origin = relative.then(|| overlay.sample(Duration::ZERO));
+        Self {
+            base,
+            overlay,
+            origin,
+            _marker: PhantomData,
+        }
+    }
+}
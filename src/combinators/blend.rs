@@ -0,0 +1,69 @@
+use crate::{lerp_components, Animatable, Animation, BoundedAnimation};
+use std::{marker::PhantomData, time::Duration};
+
+/// See [`Animation::blend`] for details.
+#[derive(Debug)]
+pub struct Blend<A, B, W, V>
+where
+    A: Animation<V>,
+    B: Animation<V>,
+    W: Animation<f64>,
+    V: Animatable,
+{
+    a: A,
+    b: B,
+    weight: W,
+    _marker: PhantomData<V>,
+}
+
+impl<A, B, W, V> Animation<V> for Blend<A, B, W, V>
+where
+    A: Animation<V>,
+    B: Animation<V>,
+    W: Animation<f64>,
+    V: Animatable,
+{
+    fn sample(&self, elapsed: Duration) -> V {
+        lerp_components(
+            self.a.sample(elapsed),
+            self.b.sample(elapsed),
+            self.weight.sample(elapsed),
+        )
+    }
+}
+
+impl<A, B, W, V> BoundedAnimation<V> for Blend<A, B, W, V>
+where
+    A: BoundedAnimation<V>,
+    B: BoundedAnimation<V>,
+    W: BoundedAnimation<f64>,
+    V: Animatable,
+{
+    fn duration(&self) -> Duration {
+        self.a
+            .duration()
+            .max(self.b.duration())
+            .max(self.weight.duration())
+    }
+}
+
+impl<A, B, W, V> Blend<A, B, W, V>
+where
+    A: Animation<V>,
+    B: Animation<V>,
+    W: Animation<f64>,
+    V: Animatable,
+{
+    pub(crate) fn new(a: A, b: B, weight: W) -> Self {
+        Self {
+            a,
+            b,
+            weight,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn weight(&self, elapsed: Duration) -> f64 {
+        self.weight.sample(elapsed)
+    }
+}
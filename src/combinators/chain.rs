@@ -28,6 +28,15 @@ where
             self.b.sample(elapsed - inflection)
         }
     }
+
+    fn sample_derivative(&self, elapsed: Duration) -> Option<V> {
+        let inflection = self.a.duration();
+        if elapsed < inflection {
+            self.a.sample_derivative(elapsed)
+        } else {
+            self.b.sample_derivative(elapsed - inflection)
+        }
+    }
 }
 
 impl<A, B, V> BoundedAnimation<V> for Chain<A, B, V>
@@ -25,6 +25,15 @@ where
             self.cutoff
         })
     }
+
+    fn sample_derivative(&self, elapsed: Duration) -> Option<V> {
+        if elapsed <= self.cutoff {
+            self.anim.sample_derivative(elapsed)
+        } else {
+            let value = self.anim.sample(self.cutoff);
+            Some(value.sub(value))
+        }
+    }
 }
 
 impl<A, V> BoundedAnimation<V> for Cutoff<A, V>
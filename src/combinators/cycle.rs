@@ -21,6 +21,12 @@ where
         let progress = elapsed.as_secs_f64() % self.anim.duration().as_secs_f64();
         self.anim.sample(Duration::from_secs_f64(progress))
     }
+
+    fn sample_derivative(&self, elapsed: Duration) -> Option<V> {
+        let progress = elapsed.as_secs_f64() % self.anim.duration().as_secs_f64();
+        self.anim
+            .sample_derivative(Duration::from_secs_f64(progress))
+    }
 }
 
 impl<A, V> Cycle<A, V>
@@ -0,0 +1,48 @@
+use crate::{Animatable, Animation, BoundedAnimation};
+use std::{marker::PhantomData, time::Duration};
+
+/// See [`Animation::delay`] for details.
+#[derive(Debug)]
+pub struct Delay<A, V>
+where
+    A: Animation<V>,
+    V: Animatable,
+{
+    anim: A,
+    delay: Duration,
+    _marker: PhantomData<V>,
+}
+
+impl<A, V> Animation<V> for Delay<A, V>
+where
+    A: Animation<V>,
+    V: Animatable,
+{
+    fn sample(&self, elapsed: Duration) -> V {
+        self.anim.sample(elapsed.saturating_sub(self.delay))
+    }
+}
+
+impl<A, V> BoundedAnimation<V> for Delay<A, V>
+where
+    A: BoundedAnimation<V>,
+    V: Animatable,
+{
+    fn duration(&self) -> Duration {
+        self.delay + self.anim.duration()
+    }
+}
+
+impl<A, V> Delay<A, V>
+where
+    A: Animation<V>,
+    V: Animatable,
+{
+    pub(crate) fn new(anim: A, delay: Duration) -> Self {
+        Self {
+            anim,
+            delay,
+            _marker: PhantomData,
+        }
+    }
+}
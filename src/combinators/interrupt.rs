@@ -95,17 +95,18 @@ where
         let interrupt_v = a.sample(interrupt_t);
         let sample_duration = Duration::from_secs_f64(SAMPLE_DELTA);
 
-        let velocity = a
-            .sample(interrupt_t + sample_duration)
-            .zip_map(
-                a.sample(
-                    (interrupt_t < sample_duration)
-                        .then(|| Duration::ZERO)
-                        .unwrap_or_else(|| interrupt_t - Duration::from_secs_f64(SAMPLE_DELTA)),
-                ),
-                |n, p| n - p,
-            )
-            .map(|a| a * V::cast_component(0.5 / SAMPLE_DELTA));
+        let velocity = a.sample_derivative(interrupt_t).unwrap_or_else(|| {
+            a.sample(interrupt_t + sample_duration)
+                .zip_map(
+                    a.sample(
+                        (interrupt_t < sample_duration)
+                            .then(|| Duration::ZERO)
+                            .unwrap_or_else(|| interrupt_t - Duration::from_secs_f64(SAMPLE_DELTA)),
+                    ),
+                    |n, p| n - p,
+                )
+                .map(|a| a * V::cast_component(0.5 / SAMPLE_DELTA))
+        });
 
         let linear = Linear::new(interrupt_v, velocity);
 
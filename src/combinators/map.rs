@@ -0,0 +1,74 @@
+use crate::{Animatable, Animation, BoundedAnimation};
+use std::{
+    fmt::{self, Debug},
+    marker::PhantomData,
+    time::Duration,
+};
+
+/// See [`Animation::map`] for details.
+pub struct Map<A, F, V, W>
+where
+    A: Animation<V>,
+    F: Fn(V) -> W,
+    V: Animatable,
+    W: Animatable,
+{
+    anim: A,
+    f: F,
+    _marker: PhantomData<(V, W)>,
+}
+
+impl<A, F, V, W> Debug for Map<A, F, V, W>
+where
+    A: Animation<V>,
+    F: Fn(V) -> W,
+    V: Animatable,
+    W: Animatable,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Map")
+            .field("anim", &self.anim)
+            .field("f", &"..")
+            .finish()
+    }
+}
+
+impl<A, F, V, W> Animation<W> for Map<A, F, V, W>
+where
+    A: Animation<V>,
+    F: Fn(V) -> W,
+    V: Animatable,
+    W: Animatable,
+{
+    fn sample(&self, elapsed: Duration) -> W {
+        (self.f)(self.anim.sample(elapsed))
+    }
+}
+
+impl<A, F, V, W> BoundedAnimation<W> for Map<A, F, V, W>
+where
+    A: BoundedAnimation<V>,
+    F: Fn(V) -> W,
+    V: Animatable,
+    W: Animatable,
+{
+    fn duration(&self) -> Duration {
+        self.anim.duration()
+    }
+}
+
+impl<A, F, V, W> Map<A, F, V, W>
+where
+    A: Animation<V>,
+    F: Fn(V) -> W,
+    V: Animatable,
+    W: Animatable,
+{
+    pub(crate) fn new(anim: A, f: F) -> Self {
+        Self {
+            anim,
+            f,
+            _marker: PhantomData,
+        }
+    }
+}
@@ -1,7 +1,21 @@
+mod additive;
+mod blend;
 mod chain;
 mod cutoff;
 mod cycle;
+mod delay;
 mod interrupt;
+mod map;
+mod offset;
+mod remap;
 mod rev;
+mod scrub;
+mod sequence;
+mod speed;
+mod stagger;
+mod zip;
 
-pub use self::{chain::*, cutoff::*, cycle::*, interrupt::*, rev::*};
+pub use self::{
+    additive::*, blend::*, chain::*, cutoff::*, cycle::*, delay::*, interrupt::*, map::*,
+    offset::*, remap::*, rev::*, scrub::*, sequence::*, speed::*, stagger::*, zip::*,
+};
@@ -0,0 +1,48 @@
+use crate::{Animatable, Animation, BoundedAnimation};
+use std::{marker::PhantomData, time::Duration};
+
+/// See [`Animation::offset`] for details.
+#[derive(Debug)]
+pub struct Offset<A, V>
+where
+    A: Animation<V>,
+    V: Animatable,
+{
+    anim: A,
+    offset: Duration,
+    _marker: PhantomData<V>,
+}
+
+impl<A, V> Animation<V> for Offset<A, V>
+where
+    A: Animation<V>,
+    V: Animatable,
+{
+    fn sample(&self, elapsed: Duration) -> V {
+        self.anim.sample(elapsed + self.offset)
+    }
+}
+
+impl<A, V> BoundedAnimation<V> for Offset<A, V>
+where
+    A: BoundedAnimation<V>,
+    V: Animatable,
+{
+    fn duration(&self) -> Duration {
+        self.anim.duration().saturating_sub(self.offset)
+    }
+}
+
+impl<A, V> Offset<A, V>
+where
+    A: Animation<V>,
+    V: Animatable,
+{
+    pub(crate) fn new(anim: A, offset: Duration) -> Self {
+        Self {
+            anim,
+            offset,
+            _marker: PhantomData,
+        }
+    }
+}
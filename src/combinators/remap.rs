@@ -0,0 +1,141 @@
+use crate::{Animatable, Animation, BoundedAnimation};
+use std::{marker::PhantomData, time::Duration};
+
+/// See [`Animation::remap`] for details.
+#[derive(Debug)]
+pub struct Remap<A, T, V>
+where
+    A: Animation<V>,
+    T: Animation<f64>,
+    V: Animatable,
+{
+    anim: A,
+    time: T,
+    _marker: PhantomData<V>,
+}
+
+impl<A, T, V> Animation<V> for Remap<A, T, V>
+where
+    A: Animation<V>,
+    T: Animation<f64>,
+    V: Animatable,
+{
+    fn sample(&self, elapsed: Duration) -> V {
+        // Negative (and NaN) times are clamped, since animations can't be
+        // sampled before they start
+        let remapped = self.time.sample(elapsed);
+        self.anim.sample(if remapped > 0.0 {
+            Duration::try_from_secs_f64(remapped).unwrap_or(Duration::MAX)
+        } else {
+            Duration::ZERO
+        })
+    }
+}
+
+impl<A, T, V> BoundedAnimation<V> for Remap<A, T, V>
+where
+    A: Animation<V>,
+    T: BoundedAnimation<f64>,
+    V: Animatable,
+{
+    fn duration(&self) -> Duration {
+        // Once the time stops changing, the sampled value does too
+        self.time.duration()
+    }
+}
+
+impl<A, T, V> Remap<A, T, V>
+where
+    A: Animation<V>,
+    T: Animation<f64>,
+    V: Animatable,
+{
+    pub(crate) fn new(anim: A, time: T) -> Self {
+        Self {
+            anim,
+            time,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// See [`BoundedAnimation::remap_progress`] for details.
+#[derive(Debug)]
+pub struct RemapProgress<A, T, V>
+where
+    A: BoundedAnimation<V>,
+    T: Animation<f64>,
+    V: Animatable,
+{
+    anim: A,
+    progress: T,
+    _marker: PhantomData<V>,
+}
+
+impl<A, T, V> Animation<V> for RemapProgress<A, T, V>
+where
+    A: BoundedAnimation<V>,
+    T: Animation<f64>,
+    V: Animatable,
+{
+    fn sample(&self, elapsed: Duration) -> V {
+        self.anim.sample_progress(self.progress.sample(elapsed))
+    }
+}
+
+impl<A, T, V> BoundedAnimation<V> for RemapProgress<A, T, V>
+where
+    A: BoundedAnimation<V>,
+    T: BoundedAnimation<f64>,
+    V: Animatable,
+{
+    fn duration(&self) -> Duration {
+        self.progress.duration()
+    }
+}
+
+impl<A, T, V> RemapProgress<A, T, V>
+where
+    A: BoundedAnimation<V>,
+    T: Animation<f64>,
+    V: Animatable,
+{
+    pub(crate) fn new(anim: A, progress: T) -> Self {
+        Self {
+            anim,
+            progress,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        constant::Constant,
+        interval::{Frame, Interval},
+        Animation as _, BoundedAnimation as _,
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn test_out_of_range_time() {
+        let anim = Interval::linear(
+            Frame::new(Duration::ZERO, 1.0),
+            Frame::new(Duration::from_secs(1), 2.0),
+        );
+        for (time, expected) in [
+            (f64::NAN, 1.0),
+            (f64::NEG_INFINITY, 1.0),
+            (f64::INFINITY, 2.0),
+            (1e30, 2.0),
+        ] {
+            let remap = anim.clone().remap(Constant::new(time));
+            assert_eq!(remap.sample(Duration::ZERO), expected, "{}", time);
+        }
+        for progress in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let remap = anim.clone().remap_progress(Constant::new(progress));
+            assert_eq!(remap.sample(Duration::ZERO), 1.0, "{}", progress);
+        }
+    }
+}
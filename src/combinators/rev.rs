@@ -1,4 +1,5 @@
 use crate::{Animatable, Animation, BoundedAnimation};
+use gee::en::Num as _;
 use std::{marker::PhantomData, time::Duration};
 
 /// See [`BoundedAnimation::rev`] for details.
@@ -24,6 +25,18 @@ where
                 .unwrap_or_else(|| Duration::ZERO),
         )
     }
+
+    fn sample_derivative(&self, elapsed: Duration) -> Option<V> {
+        if elapsed <= self.duration() {
+            // Playing backwards flips the direction of travel
+            self.anim
+                .sample_derivative(self.duration() - elapsed)
+                .map(|derivative| derivative.map(|c| (-c.to_f64()).cast()))
+        } else {
+            let value = self.anim.sample(Duration::ZERO);
+            Some(value.sub(value))
+        }
+    }
 }
 
 impl<A, V> BoundedAnimation<V> for Rev<A, V>
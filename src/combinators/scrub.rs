@@ -0,0 +1,141 @@
+use crate::{Animatable, BoundedAnimation};
+use std::{marker::PhantomData, time::Duration};
+
+// Sampling delta used to find the slope at either end of the animation
+const EXTEND_DELTA: f64 = 1e-5;
+
+/// How a [`Scrub`] treats inputs outside of its input range.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ScrubMode {
+    /// Hold the first/last value.
+    Clamp,
+    /// Keep moving at the velocity of the first/last value.
+    Extend,
+    /// Start over from the beginning every time the range is exceeded.
+    Repeat,
+    /// Play backwards and forwards, like a ping-pong ball.
+    Mirror,
+}
+
+/// See [`BoundedAnimation::scrub`] for details.
+#[derive(Debug)]
+pub struct Scrub<A, V>
+where
+    A: BoundedAnimation<V>,
+    V: Animatable,
+{
+    anim: A,
+    input_start: f64,
+    input_end: f64,
+    mode: ScrubMode,
+    _marker: PhantomData<V>,
+}
+
+impl<A, V> Scrub<A, V>
+where
+    A: BoundedAnimation<V>,
+    V: Animatable,
+{
+    pub(crate) fn new(anim: A, input_start: f64, input_end: f64, mode: ScrubMode) -> Self {
+        Self {
+            anim,
+            input_start,
+            input_end,
+            mode,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The progress through the animation for `input`, before applying the
+    /// [`ScrubMode`].
+    pub fn progress(&self, input: f64) -> f64 {
+        let range = self.input_end - self.input_start;
+        if range == 0.0 {
+            // A range of zero width is a step from the start to the end
+            if input >= self.input_start {
+                1.0
+            } else {
+                0.0
+            }
+        } else {
+            (input - self.input_start) / range
+        }
+    }
+
+    /// Samples the animation at the point on its timeline corresponding to
+    /// `input`.
+    ///
+    /// Inputs that give NaN or infinite progress sample the start.
+    pub fn sample_input(&self, input: f64) -> V {
+        let progress = self.progress(input);
+        let progress = if progress.is_finite() { progress } else { 0.0 };
+        match self.mode {
+            ScrubMode::Clamp => self.anim.sample_progress(progress),
+            ScrubMode::Extend => {
+                if progress < 0.0 {
+                    self.extrapolate(Duration::ZERO, -progress)
+                } else if progress > 1.0 {
+                    self.extrapolate(self.anim.duration(), progress - 1.0)
+                } else {
+                    self.anim.sample_progress(progress)
+                }
+            }
+            ScrubMode::Repeat => self.anim.sample_progress(progress.rem_euclid(1.0)),
+            ScrubMode::Mirror => {
+                let progress = progress.rem_euclid(2.0);
+                self.anim.sample_progress(if progress > 1.0 {
+                    2.0 - progress
+                } else {
+                    progress
+                })
+            }
+        }
+    }
+
+    // Continues past `edge` in a straight line, `overshoot` (as a fraction of
+    // the duration) beyond it
+    fn extrapolate(&self, edge: Duration, overshoot: f64) -> V {
+        let duration = self.anim.duration();
+        let delta = Duration::from_secs_f64(EXTEND_DELTA).min(duration);
+        let edge_value = self.anim.sample(edge);
+        if delta.is_zero() {
+            return edge_value;
+        }
+        let inner_value = self
+            .anim
+            .sample(if edge.is_zero() { delta } else { edge - delta });
+        let scale = duration.as_secs_f64() * overshoot / delta.as_secs_f64();
+        edge_value.zip_map(inner_value, |e, i| e + (e - i) * V::cast_component(scale))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interval::{Frame, Interval};
+
+    #[test]
+    fn test_non_finite_input() {
+        let anim = Interval::linear(
+            Frame::new(Duration::ZERO, 1.0),
+            Frame::new(Duration::from_secs(1), 2.0),
+        );
+        for progress in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert_eq!(anim.sample_progress(progress), 1.0);
+        }
+        for mode in [
+            ScrubMode::Clamp,
+            ScrubMode::Extend,
+            ScrubMode::Repeat,
+            ScrubMode::Mirror,
+        ] {
+            let scrub = anim.clone().scrub(0.0, 100.0, mode);
+            for input in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+                assert_eq!(scrub.sample_input(input), 1.0, "{:?} at {}", mode, input);
+            }
+            // Dividing by a tiny range overflows
+            let scrub = anim.clone().scrub(0.0, f64::MIN_POSITIVE, mode);
+            assert_eq!(scrub.sample_input(f64::MAX), 1.0, "{:?}", mode);
+        }
+    }
+}
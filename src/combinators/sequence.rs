@@ -0,0 +1,123 @@
+use crate::{Animatable, Animation, BoundedAnimation};
+use std::time::Duration;
+
+/// Plays a dynamic list of bounded animations one after another.
+///
+/// This is like [`BoundedAnimation::chain`], but the number of animations
+/// doesn't need to be known at compile time, and animations can be added or
+/// removed during playback.
+#[derive(Debug)]
+pub struct Sequence<V: Animatable> {
+    anims: Vec<Box<dyn BoundedAnimation<V>>>,
+    // The accumulated duration at the end of each animation
+    ends: Vec<Duration>,
+}
+
+impl<V: Animatable> Default for Sequence<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Animatable> Sequence<V> {
+    pub fn new() -> Self {
+        Self {
+            anims: vec![],
+            ends: vec![],
+        }
+    }
+
+    pub fn from_animations(anims: impl IntoIterator<Item = Box<dyn BoundedAnimation<V>>>) -> Self {
+        let mut sequence = Self {
+            anims: anims.into_iter().collect(),
+            ends: vec![],
+        };
+        sequence.accumulate_ends();
+        sequence
+    }
+
+    pub fn with_animation(mut self, anim: Box<dyn BoundedAnimation<V>>) -> Self {
+        self.push(anim);
+        self
+    }
+
+    /// Appends an animation to the end of the sequence.
+    pub fn push(&mut self, anim: Box<dyn BoundedAnimation<V>>) -> &mut Self {
+        self.ends.push(self.duration() + anim.duration());
+        self.anims.push(anim);
+        self
+    }
+
+    /// Inserts an animation at `index`, delaying every animation after it.
+    ///
+    /// # Panics
+    /// If `index > len`.
+    pub fn insert(&mut self, index: usize, anim: Box<dyn BoundedAnimation<V>>) -> &mut Self {
+        self.anims.insert(index, anim);
+        self.accumulate_ends();
+        self
+    }
+
+    /// Removes the animation at `index`, moving every animation after it
+    /// earlier.
+    ///
+    /// # Panics
+    /// If `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Box<dyn BoundedAnimation<V>> {
+        let anim = self.anims.remove(index);
+        self.accumulate_ends();
+        anim
+    }
+
+    pub fn len(&self) -> usize {
+        self.anims.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.anims.is_empty()
+    }
+
+    /// The elapsed duration at which the animation at `index` starts.
+    pub fn start_of(&self, index: usize) -> Duration {
+        index
+            .checked_sub(1)
+            .map(|prev| self.ends[prev])
+            .unwrap_or_default()
+    }
+
+    /// The index of the animation playing at `elapsed`.
+    ///
+    /// Once the sequence has ended, this is the index of the last animation.
+    pub fn current_index(&self, elapsed: Duration) -> Option<usize> {
+        // The first animation that hasn't ended yet, found via binary search
+        let index = self.ends.partition_point(|end| *end <= elapsed);
+        (!self.anims.is_empty()).then(|| index.min(self.anims.len() - 1))
+    }
+
+    fn accumulate_ends(&mut self) {
+        let mut total = Duration::ZERO;
+        self.ends = self
+            .anims
+            .iter()
+            .map(|anim| {
+                total += anim.duration();
+                total
+            })
+            .collect();
+    }
+}
+
+impl<V: Animatable> Animation<V> for Sequence<V> {
+    fn sample(&self, elapsed: Duration) -> V {
+        let index = self
+            .current_index(elapsed)
+            .expect("tried to sample empty `Sequence`");
+        self.anims[index].sample(elapsed - self.start_of(index))
+    }
+}
+
+impl<V: Animatable> BoundedAnimation<V> for Sequence<V> {
+    fn duration(&self) -> Duration {
+        self.ends.last().copied().unwrap_or_default()
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{Animatable, Animation, BoundedAnimation};
+use std::{marker::PhantomData, time::Duration};
+
+/// See [`Animation::speed`] for details.
+#[derive(Debug)]
+pub struct Speed<A, V>
+where
+    A: Animation<V>,
+    V: Animatable,
+{
+    anim: A,
+    factor: f64,
+    _marker: PhantomData<V>,
+}
+
+impl<A, V> Animation<V> for Speed<A, V>
+where
+    A: Animation<V>,
+    V: Animatable,
+{
+    fn sample(&self, elapsed: Duration) -> V {
+        self.anim.sample(elapsed.mul_f64(self.factor))
+    }
+}
+
+impl<A, V> BoundedAnimation<V> for Speed<A, V>
+where
+    A: BoundedAnimation<V>,
+    V: Animatable,
+{
+    fn duration(&self) -> Duration {
+        self.anim.duration().div_f64(self.factor)
+    }
+}
+
+impl<A, V> Speed<A, V>
+where
+    A: Animation<V>,
+    V: Animatable,
+{
+    pub(crate) fn new(anim: A, factor: f64) -> Self {
+        assert!(
+            factor.is_finite() && factor > 0.0,
+            "playback speed must be positive and finite, but you specified {}",
+            factor
+        );
+        Self {
+            anim,
+            factor,
+            _marker: PhantomData,
+        }
+    }
+}
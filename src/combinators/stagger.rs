@@ -0,0 +1,124 @@
+use crate::{ease::Ease, Animatable, BoundedAnimation};
+use gee::en::Num as _;
+use std::{marker::PhantomData, time::Duration};
+
+/// Which items in a [`Stagger`] start first.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StaggerOrigin {
+    /// The first item starts first, and the last item starts last.
+    Start,
+    /// The last item starts first, and the first item starts last.
+    End,
+    /// The middle item(s) start first, spreading outward.
+    Center,
+    /// The first and last items start first, converging on the middle.
+    Edges,
+}
+
+/// A group of bounded animations started one after another, such as the
+/// entrance of each card in a list.
+#[derive(Debug)]
+pub struct Stagger<A, V>
+where
+    A: BoundedAnimation<V>,
+    V: Animatable,
+{
+    anims: Vec<A>,
+    interval: Duration,
+    origin: StaggerOrigin,
+    ease: Option<Ease>,
+    _marker: PhantomData<V>,
+}
+
+impl<A, V> Stagger<A, V>
+where
+    A: BoundedAnimation<V>,
+    V: Animatable,
+{
+    /// Staggers `anims`, with `interval` between the starts of neighboring
+    /// items.
+    pub fn new(anims: impl IntoIterator<Item = A>, interval: Duration) -> Self {
+        Self {
+            anims: anims.into_iter().collect(),
+            interval,
+            origin: StaggerOrigin::Start,
+            ease: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Staggers `count` animations produced by calling `f` with each index.
+    pub fn from_fn(count: usize, interval: Duration, f: impl FnMut(usize) -> A) -> Self {
+        Self::new((0..count).map(f), interval)
+    }
+
+    pub fn with_origin(mut self, origin: StaggerOrigin) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Eases the distribution of delays, so that the gaps between starts
+    /// aren't uniform. The total spread of delays stays the same.
+    pub fn with_ease(mut self, ease: Option<Ease>) -> Self {
+        self.ease = ease;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.anims.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.anims.is_empty()
+    }
+
+    pub fn animations(&self) -> &[A] {
+        &self.anims
+    }
+
+    /// How long the item at `index` waits before starting.
+    pub fn delay(&self, index: usize) -> Duration {
+        let last = self.anims.len().saturating_sub(1);
+        if last == 0 {
+            return Duration::ZERO;
+        }
+        let center = last.to_f64() / 2.0;
+        let distance_from_center = (index.to_f64() - center).abs() / center;
+        // How far through the stagger this item is, and how many intervals
+        // the stagger spans
+        let (position, steps) = match self.origin {
+            StaggerOrigin::Start => (index.to_f64() / last.to_f64(), last.to_f64()),
+            StaggerOrigin::End => ((last - index).to_f64() / last.to_f64(), last.to_f64()),
+            StaggerOrigin::Center => (distance_from_center, center),
+            StaggerOrigin::Edges => (1.0 - distance_from_center, center),
+        };
+        let eased = self
+            .ease
+            .as_ref()
+            .map(|ease| ease.ease(position))
+            .unwrap_or(position);
+        self.interval.mul_f64((eased * steps).max(0.0))
+    }
+
+    /// Samples the item at `index`, accounting for its delay.
+    pub fn sample_index(&self, index: usize, elapsed: Duration) -> V {
+        self.anims[index].sample(elapsed.saturating_sub(self.delay(index)))
+    }
+
+    /// Samples every item, accounting for their delays.
+    pub fn sample(&self, elapsed: Duration) -> Vec<V> {
+        (0..self.anims.len())
+            .map(|index| self.sample_index(index, elapsed))
+            .collect()
+    }
+
+    /// The duration until every item has finished.
+    pub fn duration(&self) -> Duration {
+        self.anims
+            .iter()
+            .enumerate()
+            .map(|(index, anim)| self.delay(index) + anim.duration())
+            .max()
+            .unwrap_or_default()
+    }
+}
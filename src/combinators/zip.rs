@@ -0,0 +1,107 @@
+use crate::{Animatable, Animation, BoundedAnimation, ComponentWise};
+use gee::en::Num as _;
+use std::{marker::PhantomData, time::Duration};
+
+/// A tuple of (potentially heterogeneous) [`Animatable`]s, as produced by
+/// [`Zip`].
+///
+/// Tuples can't be `Animatable` directly, since same-typed scalar pairs
+/// already are. The components of every value in the tuple are exposed as
+/// `f64`s, so that a `Zipped` can still be interrupted, chained, etc.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Zipped<T>(pub T);
+
+/// See [`Animation::zip`] for details.
+///
+/// `T` is a tuple of 2 to 4 animations, and `V` is the corresponding tuple of
+/// values. For more than 2 animations, use [`Zip::new`] directly.
+#[derive(Debug)]
+pub struct Zip<T, V> {
+    anims: T,
+    _marker: PhantomData<V>,
+}
+
+impl<T, V> Zip<T, V> {
+    /// Zips a tuple of animations together.
+    ///
+    /// # Examples
+    /// ```
+    /// use celerity::{Animation as _, Interval, Zip, Zipped};
+    /// use std::time::Duration;
+    ///
+    /// let position = Interval::from_values(
+    ///     Duration::from_secs(1),
+    ///     gee::Point::new(0.0f32, 0.0),
+    ///     gee::Point::new(10.0, 0.0),
+    ///     None,
+    /// );
+    /// let scale = Interval::from_values(Duration::from_secs(1), 1.0f32, 2.0, None);
+    /// let opacity = Interval::from_values(Duration::from_secs(2), 0.0f64, 1.0, None);
+    ///
+    /// let anim = Zip::new((position, scale, opacity));
+    /// let Zipped((position, scale, opacity)) = anim.sample(Duration::from_secs(1));
+    /// ```
+    pub fn new(anims: T) -> Self {
+        Self {
+            anims,
+            _marker: PhantomData,
+        }
+    }
+}
+
+macro_rules! zip_impls {
+    ( $( ($a:ident, $v:ident, $i:tt) ),+ ) => {
+        impl<$($v: Animatable),+> ComponentWise for Zipped<($($v,)+)> {
+            type Component = f64;
+
+            fn map<F>(self, f: F) -> Self
+            where
+                F: Fn(Self::Component) -> Self::Component,
+            {
+                Zipped(($(
+                    (self.0).$i.map(|c| $v::cast_component(f(c.to_f64()))),
+                )+))
+            }
+
+            fn zip_map<F>(self, other: Self, f: F) -> Self
+            where
+                F: Fn(Self::Component, Self::Component) -> Self::Component,
+            {
+                Zipped(($(
+                    (self.0).$i.zip_map((other.0).$i, |a, b| {
+                        $v::cast_component(f(a.to_f64(), b.to_f64()))
+                    }),
+                )+))
+            }
+        }
+
+        impl<$($v: Animatable),+> Animatable for Zipped<($($v,)+)> {
+            fn distance_to(self, other: Self) -> f64 {
+                (0.0 $( + (self.0).$i.distance_to((other.0).$i).powi(2) )+).sqrt()
+            }
+        }
+
+        impl<$($a,)+ $($v),+> Animation<Zipped<($($v,)+)>> for Zip<($($a,)+), ($($v,)+)>
+        where
+            $($a: Animation<$v>, $v: Animatable,)+
+        {
+            fn sample(&self, elapsed: Duration) -> Zipped<($($v,)+)> {
+                Zipped(($( self.anims.$i.sample(elapsed), )+))
+            }
+        }
+
+        impl<$($a,)+ $($v),+> BoundedAnimation<Zipped<($($v,)+)>> for Zip<($($a,)+), ($($v,)+)>
+        where
+            $($a: BoundedAnimation<$v>, $v: Animatable,)+
+        {
+            fn duration(&self) -> Duration {
+                // The zipped value keeps changing until the longest child stops
+                Duration::ZERO $( .max(self.anims.$i.duration()) )+
+            }
+        }
+    };
+}
+
+zip_impls!((A0, V0, 0), (A1, V1, 1));
+zip_impls!((A0, V0, 0), (A1, V1, 1), (A2, V2, 2));
+zip_impls!((A0, V0, 0), (A1, V1, 1), (A2, V2, 2), (A3, V3, 3));
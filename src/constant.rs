@@ -22,6 +22,10 @@ where
     fn sample(&self, _elapsed: Duration) -> V {
         self.value
     }
+
+    fn sample_derivative(&self, _elapsed: Duration) -> Option<V> {
+        Some(self.value.sub(self.value))
+    }
 }
 
 impl<V> BoundedAnimation<V> for Constant<V>
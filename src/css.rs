@@ -0,0 +1,381 @@
+use crate::{
+    ease::{Ease, EaseFamily, EaseMode, FamilyKind, JumpMode, Preset},
+    spline::bezier_ease::BezierEase,
+};
+use std::fmt::{self, Display};
+use thiserror::Error;
+
+// How many segments eases without a CSS equivalent are approximated with
+const CSS_SAMPLES: usize = 64;
+
+// The CSS keywords that are shorthand for `cubic-bezier()`
+const BEZIER_KEYWORDS: [(&str, BezierEase); 4] = [
+    ("ease", BezierEase::new(0.25, 0.1, 0.25, 1.0)),
+    ("ease-in", BezierEase::new(0.42, 0.0, 1.0, 1.0)),
+    ("ease-out", BezierEase::new(0.0, 0.0, 0.58, 1.0)),
+    ("ease-in-out", BezierEase::new(0.42, 0.0, 0.58, 1.0)),
+];
+
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum CssEaseError {
+    #[error("Unknown timing function `{0}`")]
+    UnknownFunction(String),
+    #[error("Missing closing parenthesis in `{0}`")]
+    Unclosed(String),
+    #[error("`{function}` takes {expected} arguments, but {actual} were given")]
+    ArgumentCount {
+        function: &'static str,
+        expected: &'static str,
+        actual: usize,
+    },
+    #[error("Invalid number `{0}`")]
+    InvalidNumber(String),
+    #[error("Invalid step position `{0}`")]
+    InvalidStepPosition(String),
+    #[error("Invalid `{function}` arguments: {message}")]
+    InvalidArguments {
+        function: &'static str,
+        message: String,
+    },
+}
+
+fn parse_number(token: &str) -> Result<f64, CssEaseError> {
+    token
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite())
+        .ok_or_else(|| CssEaseError::InvalidNumber(token.to_owned()))
+}
+
+fn parse_percentage(token: &str) -> Option<Result<f64, CssEaseError>> {
+    token
+        .strip_suffix('%')
+        .map(|number| parse_number(number).map(|number| number / 100.0))
+}
+
+fn expect_arguments(
+    function: &'static str,
+    args: &[&str],
+    range: std::ops::RangeInclusive<usize>,
+    expected: &'static str,
+) -> Result<(), CssEaseError> {
+    if range.contains(&args.len()) {
+        Ok(())
+    } else {
+        Err(CssEaseError::ArgumentCount {
+            function,
+            expected,
+            actual: args.len(),
+        })
+    }
+}
+
+fn parse_cubic_bezier(args: &[&str]) -> Result<Ease, CssEaseError> {
+    expect_arguments("cubic-bezier", args, 4..=4, "4")?;
+    let numbers = args
+        .iter()
+        .map(|arg| parse_number(arg))
+        .collect::<Result<Vec<_>, _>>()?;
+    let (ox, oy, ix, iy) = (numbers[0], numbers[1], numbers[2], numbers[3]);
+    if !(0.0..=1.0).contains(&ox) || !(0.0..=1.0).contains(&ix) {
+        return Err(CssEaseError::InvalidArguments {
+            function: "cubic-bezier",
+            message: format!(
+                "x values must be between 0 and 1, but were {} and {}",
+                ox, ix
+            ),
+        });
+    }
+    Ok(BezierEase::new_ease(ox, oy, ix, iy))
+}
+
+fn parse_steps(args: &[&str]) -> Result<Ease, CssEaseError> {
+    expect_arguments("steps", args, 1..=2, "1 or 2")?;
+    let steps = args[0]
+        .parse::<u32>()
+        .map_err(|_| CssEaseError::InvalidNumber(args[0].to_owned()))?;
+    let jump = match args.get(1).copied() {
+        None | Some("jump-end") | Some("end") => JumpMode::End,
+        Some("jump-start") | Some("start") => JumpMode::Start,
+        Some("jump-none") => JumpMode::None,
+        Some("jump-both") => JumpMode::Both,
+        Some(position) => return Err(CssEaseError::InvalidStepPosition(position.to_owned())),
+    };
+    EaseFamily::try_steps(steps, jump)
+        .map(|family| Ease::Parametric(family, EaseMode::In))
+        .map_err(|message| CssEaseError::InvalidArguments {
+            function: "steps",
+            message,
+        })
+}
+
+// https://drafts.csswg.org/css-easing-2/#the-linear-easing-function
+fn parse_linear(args: &[&str]) -> Result<Ease, CssEaseError> {
+    // Each stop is an output value, along with 0-2 input positions
+    let mut points = Vec::<(Option<f64>, f64)>::new();
+    for arg in args {
+        let mut value = None;
+        let mut positions = vec![];
+        for token in arg.split_whitespace() {
+            match parse_percentage(token) {
+                Some(position) if positions.len() < 2 => positions.push(Some(position?)),
+                None if value.is_none() => value = Some(parse_number(token)?),
+                _ => {
+                    return Err(CssEaseError::InvalidArguments {
+                        function: "linear",
+                        message: format!("`{}` isn't a valid stop", arg),
+                    })
+                }
+            }
+        }
+        let value = value.ok_or_else(|| CssEaseError::InvalidArguments {
+            function: "linear",
+            message: format!("`{}` is missing a value", arg),
+        })?;
+        if positions.is_empty() {
+            positions.push(None);
+        }
+        points.extend(positions.into_iter().map(|position| (position, value)));
+    }
+
+    // Fill in the first and last positions
+    if let Some(first) = points.first_mut() {
+        first.0.get_or_insert(0.0);
+    }
+    let largest = points
+        .iter()
+        .filter_map(|(position, _)| *position)
+        .fold(1.0, f64::max);
+    if let Some(last) = points.last_mut() {
+        last.0.get_or_insert(largest);
+    }
+
+    // Positions can't decrease
+    let mut largest = f64::NEG_INFINITY;
+    for (position, _) in &mut points {
+        if let Some(position) = position {
+            largest = position.max(largest);
+            *position = largest;
+        }
+    }
+
+    // Space out runs of missing positions evenly
+    let mut index = 0;
+    while index < points.len() {
+        if points[index].0.is_some() {
+            index += 1;
+            continue;
+        }
+        let start = index - 1;
+        let end = (index..points.len())
+            .find(|index| points[*index].0.is_some())
+            .unwrap();
+        let (from, to) = (points[start].0.unwrap(), points[end].0.unwrap());
+        for (offset, point) in points[index..end].iter_mut().enumerate() {
+            let fraction = (index + offset - start) as f64 / (end - start) as f64;
+            point.0 = Some(from + (to - from) * fraction);
+        }
+        index = end;
+    }
+
+    Ease::try_piecewise(
+        points
+            .into_iter()
+            .map(|(position, value)| (position.unwrap(), value))
+            .collect(),
+    )
+    .map_err(|message| CssEaseError::InvalidArguments {
+        function: "linear",
+        message,
+    })
+}
+
+// Formats a number as compactly as possible, since CSS doesn't need trailing
+// zeros
+fn css_number(number: f64) -> String {
+    let formatted = format!("{:.5}", number);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed == "-0" {
+        "0".to_owned()
+    } else {
+        trimmed.to_owned()
+    }
+}
+
+impl Ease {
+    /// Parses a CSS `<easing-function>`, such as `ease-in-out`,
+    /// `cubic-bezier(.17, .67, .83, .67)`, `steps(4, jump-end)` or
+    /// `linear(0, 0.25 75%, 1)`.
+    pub fn from_css(css: &str) -> Result<Self, CssEaseError> {
+        let css = css.trim().to_ascii_lowercase();
+        let (name, args) = match css.find('(') {
+            Some(open) => {
+                let args = css[open + 1..]
+                    .strip_suffix(')')
+                    .ok_or_else(|| CssEaseError::Unclosed(css.clone()))?;
+                let args = if args.trim().is_empty() {
+                    vec![]
+                } else {
+                    args.split(',').map(str::trim).collect()
+                };
+                (css[..open].trim(), Some(args))
+            }
+            None => (css.as_str(), None),
+        };
+        match (name, args.as_deref()) {
+            ("linear", None) => Ok(Self::Preset(Preset::Linear)),
+            ("step-start", None) => Ok(Self::steps(1, JumpMode::Start)),
+            ("step-end", None) => Ok(Self::steps(1, JumpMode::End)),
+            ("cubic-bezier", Some(args)) => parse_cubic_bezier(args),
+            ("steps", Some(args)) => parse_steps(args),
+            ("linear", Some(args)) => parse_linear(args),
+            (keyword, None) => BEZIER_KEYWORDS
+                .iter()
+                .find(|(name, _)| *name == keyword)
+                .map(|(_, bezier)| Self::Bezier(*bezier))
+                .ok_or_else(|| CssEaseError::UnknownFunction(keyword.to_owned())),
+            (function, Some(_)) => Err(CssEaseError::UnknownFunction(function.to_owned())),
+        }
+    }
+
+    /// Formats this ease as a CSS `<easing-function>`.
+    ///
+    /// Eases without a CSS equivalent are approximated using `linear()`.
+    pub fn to_css(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Display for Ease {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Ease::Preset(Preset::Linear) => write!(f, "linear"),
+            Ease::Bezier(bezier) => match BEZIER_KEYWORDS.iter().find(|(_, b)| b == bezier) {
+                Some((keyword, _)) => write!(f, "{}", keyword),
+                None => write!(
+                    f,
+                    "cubic-bezier({}, {}, {}, {})",
+                    css_number(bezier.ox),
+                    css_number(bezier.oy),
+                    css_number(bezier.ix),
+                    css_number(bezier.iy)
+                ),
+            },
+            Ease::Parametric(EaseFamily(FamilyKind::Steps { steps, jump }), EaseMode::In) => {
+                let jump = match jump {
+                    JumpMode::Start => "jump-start",
+                    JumpMode::End => "jump-end",
+                    JumpMode::None => "jump-none",
+                    JumpMode::Both => "jump-both",
+                };
+                write!(f, "steps({}, {})", steps, jump)
+            }
+            Ease::Piecewise(piecewise) => {
+                let stops = piecewise
+                    .points()
+                    .iter()
+                    .map(|(t, value)| format!("{} {}%", css_number(*value), css_number(t * 100.0)))
+                    .collect::<Vec<_>>();
+                write!(f, "linear({})", stops.join(", "))
+            }
+            _ => {
+                let stops = (0..=CSS_SAMPLES)
+                    .map(|i| css_number(self.ease(i as f64 / CSS_SAMPLES as f64)))
+                    .collect::<Vec<_>>();
+                write!(f, "linear({})", stops.join(", "))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let ease = Ease::from_css("cubic-bezier(.17,.67,.83,.67)").unwrap();
+        assert!(
+            matches!(ease, Ease::Bezier(bezier) if bezier == BezierEase::new(0.17, 0.67, 0.83, 0.67))
+        );
+        assert_eq!(ease.to_css(), "cubic-bezier(0.17, 0.67, 0.83, 0.67)");
+
+        for css in [
+            "linear",
+            "ease-in-out",
+            "steps(4, jump-end)",
+            "steps(3, jump-both)",
+        ] {
+            assert_eq!(Ease::from_css(css).unwrap().to_css(), css);
+        }
+        assert_eq!(
+            Ease::from_css("step-start").unwrap().to_css(),
+            "steps(1, jump-start)"
+        );
+        assert_eq!(Ease::from_css(" Steps(2) ").unwrap().ease(0.5), 0.5);
+
+        let linear = Ease::from_css("linear(0, 0.25 75%, 1)").unwrap();
+        assert_eq!(linear.ease(0.75), 0.25);
+        assert_eq!(linear.ease(0.375), 0.125);
+        assert_eq!(linear.to_css(), "linear(0 0%, 0.25 75%, 1 100%)");
+
+        // Missing positions are spaced out, and positions can't decrease
+        let linear = Ease::from_css("linear(0, 0.5 25% 75%, 0.25, 1 50%, 1)").unwrap();
+        assert!(
+            matches!(&linear, Ease::Piecewise(piecewise) if piecewise.points() == [
+                (0.0, 0.0),
+                (0.25, 0.5),
+                (0.75, 0.5),
+                (0.75, 0.25),
+                (0.75, 1.0),
+                (1.0, 1.0),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_approximate() {
+        let css = Ease::out_bounce().to_css();
+        let approximation = Ease::from_css(&css).unwrap();
+        for i in 0..=CSS_SAMPLES {
+            let t = i as f64 / CSS_SAMPLES as f64;
+            assert!((approximation.ease(t) - Ease::out_bounce().ease(t)).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            Ease::from_css("ease-sideways").unwrap_err(),
+            CssEaseError::UnknownFunction("ease-sideways".to_owned())
+        );
+        assert_eq!(
+            Ease::from_css("cubic-bezier(0, 1, 1").unwrap_err(),
+            CssEaseError::Unclosed("cubic-bezier(0, 1, 1".to_owned())
+        );
+        assert_eq!(
+            Ease::from_css("cubic-bezier(0, 1, 1)").unwrap_err(),
+            CssEaseError::ArgumentCount {
+                function: "cubic-bezier",
+                expected: "4",
+                actual: 3
+            }
+        );
+        assert!(matches!(
+            Ease::from_css("cubic-bezier(0, 1, 1.5, 1)"),
+            Err(CssEaseError::InvalidArguments { .. })
+        ));
+        assert_eq!(
+            Ease::from_css("steps(2, jump-sideways)").unwrap_err(),
+            CssEaseError::InvalidStepPosition("jump-sideways".to_owned())
+        );
+        assert!(matches!(
+            Ease::from_css("steps(1, jump-none)"),
+            Err(CssEaseError::InvalidArguments { .. })
+        ));
+        assert!(matches!(
+            Ease::from_css("linear(1)"),
+            Err(CssEaseError::InvalidArguments { .. })
+        ));
+    }
+}
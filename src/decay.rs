@@ -0,0 +1,344 @@
+use crate::{
+    spline::bezier_path::BezierPath, Animatable, Animation, BoundedAnimation, Interval, Spring,
+    SpringConfig,
+};
+use gee::en::Num as _;
+use std::time::Duration;
+
+/// A value moving with some initial velocity, decelerating exponentially due
+/// to friction.
+///
+/// The value approaches `from + velocity / friction` but never reaches it, so
+/// the animation ends once its speed drops below a threshold, and holds the
+/// value reached at that point.
+#[derive(Clone, Debug)]
+pub struct Decay<V>
+where
+    V: Animatable,
+{
+    from: V,
+    velocity: V,
+    friction: f64,
+    rest_speed: f64,
+    duration: Duration,
+}
+
+impl<V> Animation<V> for Decay<V>
+where
+    V: Animatable,
+{
+    fn sample(&self, elapsed: Duration) -> V {
+        let t = elapsed.min(self.duration).as_secs_f64();
+        let travel = (1.0 - (-self.friction * t).exp()) / self.friction;
+        self.offset(travel)
+    }
+
+    fn sample_derivative(&self, elapsed: Duration) -> Option<V> {
+        Some(self.velocity_at(elapsed))
+    }
+}
+
+impl<V> BoundedAnimation<V> for Decay<V>
+where
+    V: Animatable,
+{
+    fn duration(&self) -> Duration {
+        self.duration
+    }
+}
+
+impl<V> Decay<V>
+where
+    V: Animatable,
+{
+    /// The default friction, which roughly matches the feel of a native
+    /// scroll view.
+    pub const DEFAULT_FRICTION: f64 = 2.0;
+
+    /// The default speed (in units per second) that a decay is considered to
+    /// have stopped at.
+    pub const DEFAULT_REST_SPEED: f64 = 1.0;
+
+    /// Creates a decay starting at `from`, moving at `velocity` units per
+    /// second.
+    pub fn new(from: V, velocity: V) -> Self {
+        let mut decay = Self {
+            from,
+            velocity,
+            friction: Self::DEFAULT_FRICTION,
+            rest_speed: Self::DEFAULT_REST_SPEED,
+            duration: Duration::ZERO,
+        };
+        decay.update_duration();
+        decay
+    }
+
+    /// Sets the rate of exponential deceleration, where the velocity after
+    /// `t` seconds is `velocity * e^(-friction * t)`.
+    ///
+    /// A per-millisecond deceleration rate `r` (as used by some scroll views)
+    /// corresponds to a friction of `-1000 * ln(r)`. Defaults to
+    /// [`Self::DEFAULT_FRICTION`].
+    pub fn with_friction(mut self, friction: f64) -> Self {
+        assert!(
+            friction.is_finite() && friction > 0.0,
+            "decay friction must be positive and finite, but you specified {}",
+            friction
+        );
+        self.friction = friction;
+        self.update_duration();
+        self
+    }
+
+    /// Sets the speed below which the decay is considered to have stopped.
+    /// Defaults to [`Self::DEFAULT_REST_SPEED`].
+    pub fn with_rest_speed(mut self, rest_speed: f64) -> Self {
+        assert!(
+            rest_speed.is_finite() && rest_speed > 0.0,
+            "decay rest speed must be positive and finite, but you specified {}",
+            rest_speed
+        );
+        self.rest_speed = rest_speed;
+        self.update_duration();
+        self
+    }
+
+    /// The value that the decay would eventually reach if it never stopped.
+    pub fn projection(&self) -> V {
+        self.offset(1.0 / self.friction)
+    }
+
+    /// The rate of change of the decay at `elapsed`, in units per second.
+    pub fn velocity_at(&self, elapsed: Duration) -> V {
+        if elapsed > self.duration {
+            self.from.sub(self.from)
+        } else {
+            let decay = (-self.friction * elapsed.as_secs_f64()).exp();
+            self.velocity
+                .map(|velocity| (velocity.to_f64() * decay).cast())
+        }
+    }
+
+    // `from + velocity * travel`
+    fn offset(&self, travel: f64) -> V {
+        self.from.zip_map(self.velocity, |from, velocity| {
+            (from.to_f64() + velocity.to_f64() * travel).cast()
+        })
+    }
+
+    fn initial_speed(&self) -> f64 {
+        self.velocity.distance_to(self.from.sub(self.from))
+    }
+
+    fn update_duration(&mut self) {
+        let speed = self.initial_speed();
+        self.duration = if speed > self.rest_speed {
+            Duration::from_secs_f64((speed / self.rest_speed).ln() / self.friction)
+        } else {
+            Duration::ZERO
+        };
+    }
+}
+
+/// How a snapping [`Fling`] lands on its snap point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SnapTail {
+    /// Springs to the snap point, which can overshoot it.
+    Spring(SpringConfig),
+    /// Moves to the snap point along a Bezier curve that starts out at the
+    /// fling's full velocity (including any sideways motion), taking at most
+    /// the specified duration.
+    Bezier(Duration),
+}
+
+#[derive(Clone, Debug)]
+enum FlingKind<V: Animatable> {
+    Decay(Decay<V>),
+    Spring(Spring<V>),
+    Bezier(Interval<V>),
+}
+
+/// A [`Decay`] that can optionally land exactly on the nearest of a set of
+/// snap points.
+///
+/// Like any other animation, a fling can be caught mid-flight using
+/// [`Animation::interrupt`].
+#[derive(Clone, Debug)]
+pub struct Fling<V>
+where
+    V: Animatable,
+{
+    kind: FlingKind<V>,
+}
+
+impl<V> Animation<V> for Fling<V>
+where
+    V: Animatable,
+{
+    fn sample(&self, elapsed: Duration) -> V {
+        match &self.kind {
+            FlingKind::Decay(decay) => decay.sample(elapsed),
+            FlingKind::Spring(spring) => spring.sample(elapsed),
+            FlingKind::Bezier(interval) => interval.sample(elapsed),
+        }
+    }
+
+    fn sample_derivative(&self, elapsed: Duration) -> Option<V> {
+        match &self.kind {
+            FlingKind::Decay(decay) => decay.sample_derivative(elapsed),
+            FlingKind::Spring(spring) => spring.sample_derivative(elapsed),
+            FlingKind::Bezier(interval) => interval.sample_derivative(elapsed),
+        }
+    }
+}
+
+impl<V> BoundedAnimation<V> for Fling<V>
+where
+    V: Animatable,
+{
+    fn duration(&self) -> Duration {
+        match &self.kind {
+            FlingKind::Decay(decay) => decay.duration(),
+            FlingKind::Spring(spring) => spring.duration(),
+            FlingKind::Bezier(interval) => interval.duration(),
+        }
+    }
+}
+
+impl<V> From<Decay<V>> for Fling<V>
+where
+    V: Animatable,
+{
+    fn from(decay: Decay<V>) -> Self {
+        Self::new(decay)
+    }
+}
+
+impl<V> Fling<V>
+where
+    V: Animatable,
+{
+    /// A fling that simply decays, without snapping.
+    pub fn new(decay: Decay<V>) -> Self {
+        Self {
+            kind: FlingKind::Decay(decay),
+        }
+    }
+
+    /// A fling that lands exactly on whichever of `snap_points` is closest to
+    /// where `decay` would've ended up.
+    ///
+    /// If there are no snap points, this is the same as [`Fling::new`].
+    pub fn snapping(decay: Decay<V>, snap_points: &[V], tail: SnapTail) -> Self {
+        let projection = decay.projection();
+        let snap_point = snap_points.iter().copied().min_by(|a, b| {
+            a.distance_to(projection)
+                .total_cmp(&b.distance_to(projection))
+        });
+        let snap_point = match snap_point {
+            Some(snap_point) => snap_point,
+            None => return Self::new(decay),
+        };
+        let (from, velocity) = (decay.from, decay.velocity);
+        let kind = match tail {
+            SnapTail::Spring(config) => {
+                FlingKind::Spring(Spring::new(from, snap_point, config).with_velocity(velocity))
+            }
+            SnapTail::Bezier(max_duration) => {
+                let distance = from.distance_to(snap_point);
+                if distance == 0.0 {
+                    FlingKind::Bezier(Interval::hold(snap_point, Duration::ZERO))
+                } else {
+                    // Keep the first handle no further away than the snap
+                    // point, so the curve doesn't swing too wide
+                    let speed = decay.initial_speed();
+                    let duration = if speed > 0.0 {
+                        max_duration.min(Duration::from_secs_f64(3.0 * distance / speed))
+                    } else {
+                        max_duration
+                    };
+                    // A cubic Bezier starts off heading towards its first
+                    // handle at 3 times the handle's distance (per duration),
+                    // and arrives at rest when its second handle is at its end
+                    let scale = duration.as_secs_f64() / 3.0;
+                    let b1 = from.zip_map(velocity, |from, velocity| {
+                        (from.to_f64() + velocity.to_f64() * scale).cast()
+                    });
+                    FlingKind::Bezier(Interval::new(
+                        Duration::ZERO,
+                        duration,
+                        from,
+                        snap_point,
+                        None,
+                        Some(BezierPath::new(b1, snap_point)),
+                        None,
+                    ))
+                }
+            }
+        };
+        Self { kind }
+    }
+
+    /// Where the fling ends up.
+    pub fn destination(&self) -> V {
+        self.sample(self.duration())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decay() {
+        let decay = Decay::new(10.0f64, 1000.0);
+        assert_eq!(decay.sample(Duration::ZERO), 10.0);
+        assert!((decay.projection() - 510.0).abs() < 1e-9);
+        let rest = decay.sample(decay.duration());
+        let rest_speed = Decay::<f64>::DEFAULT_REST_SPEED;
+        assert!(
+            (decay.projection() - rest).abs() <= rest_speed / Decay::<f64>::DEFAULT_FRICTION + 1e-9
+        );
+        assert!(
+            (decay.velocity_at(decay.duration() - Duration::from_secs_f64(1e-9)) - rest_speed)
+                .abs()
+                < 1e-3
+        );
+        assert_eq!(decay.sample(decay.duration() * 2), rest);
+    }
+
+    #[test]
+    fn test_snapping() {
+        let snap_points = [0.0, 400.0, 800.0];
+        let decay = || Decay::new(10.0f64, 1000.0);
+        for tail in [
+            SnapTail::Spring(SpringConfig::default()),
+            SnapTail::Bezier(Duration::from_secs_f64(1.0)),
+        ] {
+            let fling = Fling::snapping(decay(), &snap_points, tail);
+            assert_eq!(fling.sample(Duration::ZERO), 10.0);
+            assert_eq!(fling.destination(), 400.0);
+
+            // The tail starts off at the fling's velocity
+            let dt = 1e-4;
+            let velocity =
+                (fling.sample(Duration::from_secs_f64(dt)) - fling.sample(Duration::ZERO)) / dt;
+            assert!((velocity - 1000.0).abs() < 10.0, "{:?}: {}", tail, velocity);
+        }
+
+        // Sideways motion carries over into the tail too
+        let decay = Decay::new((0.0f64, 0.0f64), (1000.0, 500.0));
+        let fling = Fling::snapping(
+            decay,
+            &[(400.0, 0.0)],
+            SnapTail::Bezier(Duration::from_secs_f64(1.0)),
+        );
+        let (x, y) = fling.sample_derivative(Duration::ZERO).unwrap();
+        assert!(
+            (x - 1000.0).abs() < 1e-6 && (y - 500.0).abs() < 1e-6,
+            "{:?}",
+            (x, y)
+        );
+        assert_eq!(fling.destination(), (400.0, 0.0));
+    }
+}
@@ -0,0 +1,420 @@
+use crate::{
+    ease::Ease,
+    interval::Interval,
+    interval_track::{IntervalTrack, TrackError},
+    spring::{Spring, SpringConfig},
+    Animatable, Animation, BoundedAnimation, Sequence,
+};
+use serde::{Deserialize, Serialize};
+use std::{f64::consts::TAU, rc::Rc, time::Duration};
+use thiserror::Error;
+
+/// A declarative description of a (possibly composed) animation, which can be
+/// loaded from any format supported by serde, and then built using
+/// [`Description::build`] or [`Description::build_bounded`].
+///
+/// All times are given in seconds. Nodes are externally tagged by their
+/// snake_case name, so in JSON a tween looks like
+/// `{"tween": {"from": 0.0, "to": 1.0, "duration": 0.5, "ease": "out_back"}}`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Description<V: Animatable> {
+    /// Holds `value` for `duration`.
+    Hold { value: V, duration: f64 },
+    /// Interpolates from `from` to `to` over `duration`.
+    Tween {
+        from: V,
+        to: V,
+        duration: f64,
+        #[serde(default)]
+        ease: Option<Ease>,
+    },
+    /// A serialized [`IntervalTrack`], which is validated when built.
+    Track(IntervalTrack<V>),
+    /// Springs from `from` to `to`. See [`SpringConfig::from_response`] for
+    /// the meaning of `response` and `damping_ratio`.
+    Spring {
+        from: V,
+        to: V,
+        #[serde(default)]
+        velocity: Option<V>,
+        #[serde(default)]
+        response: Option<f64>,
+        #[serde(default)]
+        damping_ratio: Option<f64>,
+    },
+    /// Plays bounded animations one after another.
+    Sequence(Vec<Description<V>>),
+    /// Cycles a bounded animation forever.
+    Cycle(Box<Description<V>>),
+    /// Plays a bounded animation `times` times.
+    Repeat {
+        times: u32,
+        anim: Box<Description<V>>,
+    },
+    /// Plays a bounded animation, and then plays it in reverse.
+    Mirror(Box<Description<V>>),
+    /// Plays a bounded animation in reverse.
+    Rev(Box<Description<V>>),
+    /// Waits for `delay` before starting `anim`.
+    Delay {
+        delay: f64,
+        anim: Box<Description<V>>,
+    },
+    /// Plays `anim` at a multiple of its normal speed.
+    Speed {
+        factor: f64,
+        anim: Box<Description<V>>,
+    },
+    /// Interrupts `from` at `at`, blending into `to` over `transition`.
+    Interrupt {
+        from: Box<Description<V>>,
+        to: Box<Description<V>>,
+        at: f64,
+        transition: f64,
+    },
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum DescriptionErrorKind {
+    #[error("`{field}` must be a non-negative number of seconds that fits in a `Duration`, but was {value}")]
+    InvalidSeconds { field: &'static str, value: f64 },
+    #[error("`{field}` must be positive and finite, but was {value}")]
+    NotPositive { field: &'static str, value: f64 },
+    #[error("A spring with a response of {response} and a damping ratio of {damping_ratio} is out of range")]
+    SpringOutOfRange { response: f64, damping_ratio: f64 },
+    #[error("This animation never ends, but only bounded animations can be used here")]
+    Unbounded,
+    #[error("Can't cycle an animation that has zero duration")]
+    ZeroDuration,
+    #[error("Sequences need at least one animation")]
+    EmptySequence,
+    #[error("Invalid track: {0}")]
+    Track(#[from] TrackError),
+}
+
+/// An error building a [`Description`], along with the path to the node that
+/// caused it, i.e. `sequence[1].mirror.tween`.
+#[derive(Debug, Error, PartialEq)]
+#[error("Failed to build `{path}`: {kind}")]
+pub struct DescriptionError {
+    pub path: String,
+    pub kind: DescriptionErrorKind,
+}
+
+enum Built<V: Animatable> {
+    Bounded(Box<dyn BoundedAnimation<V>>),
+    Unbounded(Box<dyn Animation<V>>),
+}
+
+impl<V: Animatable + 'static> Built<V> {
+    fn into_animation(self) -> Box<dyn Animation<V>> {
+        match self {
+            Self::Bounded(anim) => Box::new(anim),
+            Self::Unbounded(anim) => anim,
+        }
+    }
+}
+
+fn seconds(path: &str, field: &'static str, value: f64) -> Result<Duration, DescriptionError> {
+    Duration::try_from_secs_f64(value).map_err(|_| DescriptionError {
+        path: path.to_owned(),
+        kind: DescriptionErrorKind::InvalidSeconds { field, value },
+    })
+}
+
+fn positive(path: &str, field: &'static str, value: f64) -> Result<f64, DescriptionError> {
+    if value.is_finite() && value > 0.0 {
+        Ok(value)
+    } else {
+        Err(DescriptionError {
+            path: path.to_owned(),
+            kind: DescriptionErrorKind::NotPositive { field, value },
+        })
+    }
+}
+
+impl<V: Animatable + 'static> Description<V> {
+    /// Builds the described animation.
+    pub fn build(&self) -> Result<Box<dyn Animation<V>>, DescriptionError> {
+        self.build_at(self.name().to_owned())
+            .map(Built::into_animation)
+    }
+
+    /// Builds the described animation, failing if it never ends.
+    pub fn build_bounded(&self) -> Result<Box<dyn BoundedAnimation<V>>, DescriptionError> {
+        self.build_bounded_at(self.name().to_owned())
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Hold { .. } => "hold",
+            Self::Tween { .. } => "tween",
+            Self::Track(_) => "track",
+            Self::Spring { .. } => "spring",
+            Self::Sequence(_) => "sequence",
+            Self::Cycle(_) => "cycle",
+            Self::Repeat { .. } => "repeat",
+            Self::Mirror(_) => "mirror",
+            Self::Rev(_) => "rev",
+            Self::Delay { .. } => "delay",
+            Self::Speed { .. } => "speed",
+            Self::Interrupt { .. } => "interrupt",
+        }
+    }
+
+    // Builds a child node, where `segment` identifies the child within its
+    // parent (i.e. `[2]` or `.from`)
+    fn build_child(&self, path: &str, segment: &str) -> Result<Built<V>, DescriptionError> {
+        self.build_at(format!("{}{}.{}", path, segment, self.name()))
+    }
+
+    fn build_bounded_child(
+        &self,
+        path: &str,
+        segment: &str,
+    ) -> Result<Box<dyn BoundedAnimation<V>>, DescriptionError> {
+        self.build_bounded_at(format!("{}{}.{}", path, segment, self.name()))
+    }
+
+    fn build_bounded_at(
+        &self,
+        path: String,
+    ) -> Result<Box<dyn BoundedAnimation<V>>, DescriptionError> {
+        match self.build_at(path.clone())? {
+            Built::Bounded(anim) => Ok(anim),
+            Built::Unbounded(_) => Err(DescriptionError {
+                path,
+                kind: DescriptionErrorKind::Unbounded,
+            }),
+        }
+    }
+
+    fn build_at(&self, path: String) -> Result<Built<V>, DescriptionError> {
+        let built = match self {
+            Self::Hold { value, duration } => Built::Bounded(Box::new(Interval::hold(
+                *value,
+                seconds(&path, "duration", *duration)?,
+            ))),
+            Self::Tween {
+                from,
+                to,
+                duration,
+                ease,
+            } => Built::Bounded(Box::new(Interval::from_values(
+                seconds(&path, "duration", *duration)?,
+                *from,
+                *to,
+                ease.clone(),
+            ))),
+            Self::Track(track) => {
+                track.validate().map_err(|err| DescriptionError {
+                    path: path.clone(),
+                    kind: err.into(),
+                })?;
+                Built::Bounded(Box::new(track.clone()))
+            }
+            Self::Spring {
+                from,
+                to,
+                velocity,
+                response,
+                damping_ratio,
+            } => {
+                let default = SpringConfig::default();
+                let use_default = response.is_none() && damping_ratio.is_none();
+                let response = positive(
+                    &path,
+                    "response",
+                    response.unwrap_or_else(|| TAU / default.natural_frequency()),
+                )?;
+                let damping_ratio = positive(
+                    &path,
+                    "damping_ratio",
+                    damping_ratio.unwrap_or_else(|| default.damping_ratio()),
+                )?;
+                let out_of_range = || DescriptionError {
+                    path: path.clone(),
+                    kind: DescriptionErrorKind::SpringOutOfRange {
+                        response,
+                        damping_ratio,
+                    },
+                };
+                let config = if use_default {
+                    default
+                } else {
+                    SpringConfig::try_from_response(response, damping_ratio)
+                        .ok_or_else(out_of_range)?
+                };
+                let spring = Spring::new(*from, *to, config);
+                let spring = match velocity {
+                    Some(velocity) => spring.with_velocity(*velocity),
+                    None => spring,
+                };
+                if spring.duration() == Duration::MAX {
+                    return Err(out_of_range());
+                }
+                Built::Bounded(Box::new(spring))
+            }
+            Self::Sequence(anims) => {
+                if anims.is_empty() {
+                    return Err(DescriptionError {
+                        path,
+                        kind: DescriptionErrorKind::EmptySequence,
+                    });
+                }
+                let anims = anims
+                    .iter()
+                    .enumerate()
+                    .map(|(index, anim)| anim.build_bounded_child(&path, &format!("[{}]", index)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Built::Bounded(Box::new(Sequence::from_animations(anims)))
+            }
+            Self::Cycle(anim) => {
+                let anim = anim.build_bounded_child(&path, "")?;
+                if anim.duration().is_zero() {
+                    return Err(DescriptionError {
+                        path,
+                        kind: DescriptionErrorKind::ZeroDuration,
+                    });
+                }
+                Built::Unbounded(Box::new(anim.cycle()))
+            }
+            Self::Repeat { times, anim } => {
+                let anim = anim.build_bounded_child(&path, ".anim")?;
+                Built::Bounded(Box::new(anim.repeat(*times)))
+            }
+            Self::Mirror(anim) => {
+                let anim: Rc<dyn BoundedAnimation<V>> =
+                    Rc::from(anim.build_bounded_child(&path, "")?);
+                Built::Bounded(Box::new(anim.mirror()))
+            }
+            Self::Rev(anim) => Built::Bounded(Box::new(anim.build_bounded_child(&path, "")?.rev())),
+            Self::Delay { delay, anim } => {
+                let delay = seconds(&path, "delay", *delay)?;
+                match anim.build_child(&path, ".anim")? {
+                    Built::Bounded(anim) => Built::Bounded(Box::new(anim.delay(delay))),
+                    Built::Unbounded(anim) => Built::Unbounded(Box::new(anim.delay(delay))),
+                }
+            }
+            Self::Speed { factor, anim } => {
+                let factor = positive(&path, "factor", *factor)?;
+                match anim.build_child(&path, ".anim")? {
+                    Built::Bounded(anim) => Built::Bounded(Box::new(anim.speed(factor))),
+                    Built::Unbounded(anim) => Built::Unbounded(Box::new(anim.speed(factor))),
+                }
+            }
+            Self::Interrupt {
+                from,
+                to,
+                at,
+                transition,
+            } => {
+                let at = seconds(&path, "at", *at)?;
+                let transition = seconds(&path, "transition", *transition)?;
+                let from = from.build_child(&path, ".from")?.into_animation();
+                match to.build_child(&path, ".to")? {
+                    Built::Bounded(to) => {
+                        Built::Bounded(Box::new(from.interrupt(to, at, transition)))
+                    }
+                    Built::Unbounded(to) => {
+                        Built::Unbounded(Box::new(from.interrupt(to, at, transition)))
+                    }
+                }
+            }
+        };
+        Ok(built)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build() {
+        let description: Description<f64> = serde_json::from_str(
+            r#"{"sequence": [
+                {"tween": {"from": 0.0, "to": 1.0, "duration": 1.0}},
+                {"delay": {"delay": 0.5, "anim": {"hold": {"value": 2.0, "duration": 0.5}}}},
+                {"mirror": {"tween": {"from": 2.0, "to": 4.0, "duration": 1.0, "ease": "in_out_sine"}}}
+            ]}"#,
+        )
+        .unwrap();
+        let anim = description.build_bounded().unwrap();
+        assert_eq!(anim.duration(), Duration::from_secs_f64(4.0));
+        assert_eq!(anim.sample(Duration::from_secs_f64(0.5)), 0.5);
+        assert_eq!(anim.sample(Duration::from_secs_f64(1.25)), 2.0);
+        assert_eq!(anim.sample(Duration::from_secs_f64(3.0)), 4.0);
+
+        let cycle: Description<f64> = serde_json::from_str(
+            r#"{"cycle": {"tween": {"from": 0.0, "to": 1.0, "duration": 1.0}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            cycle.build().unwrap().sample(Duration::from_secs_f64(1.5)),
+            0.5
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |json: &str| {
+            serde_json::from_str::<Description<f64>>(json)
+                .unwrap()
+                .build_bounded()
+                .err()
+                .unwrap()
+        };
+        assert_eq!(
+            error(
+                r#"{"sequence": [
+                    {"hold": {"value": 0.0, "duration": 1.0}},
+                    {"rev": {"cycle": {"hold": {"value": 0.0, "duration": 1.0}}}}
+                ]}"#
+            ),
+            DescriptionError {
+                path: "sequence[1].rev.cycle".to_owned(),
+                kind: DescriptionErrorKind::Unbounded,
+            }
+        );
+        assert_eq!(
+            error(
+                r#"{"speed": {"factor": 0.0, "anim": {"hold": {"value": 0.0, "duration": 1.0}}}}"#
+            ),
+            DescriptionError {
+                path: "speed".to_owned(),
+                kind: DescriptionErrorKind::NotPositive {
+                    field: "factor",
+                    value: 0.0
+                },
+            }
+        );
+        assert_eq!(
+            error(r#"{"track": {"intervals": [], "track_ease": null}}"#).kind,
+            DescriptionErrorKind::Track(TrackError::Empty)
+        );
+        assert_eq!(
+            error(r#"{"hold": {"value": 0.0, "duration": 1e30}}"#).kind,
+            DescriptionErrorKind::InvalidSeconds {
+                field: "duration",
+                value: 1e30
+            }
+        );
+        assert_eq!(
+            error(r#"{"spring": {"from": 0.0, "to": 1.0, "response": 1e-200}}"#).kind,
+            DescriptionErrorKind::SpringOutOfRange {
+                response: 1e-200,
+                damping_ratio: SpringConfig::default().damping_ratio()
+            }
+        );
+        // Springs that would take too long to settle
+        assert!(matches!(
+            error(
+                r#"{"spring": {"from": 0.0, "to": 1.0, "response": 1.0, "damping_ratio": 1e-300}}"#
+            )
+            .kind,
+            DescriptionErrorKind::SpringOutOfRange { .. }
+        ));
+    }
+}
@@ -1,136 +1,1147 @@
-use crate::spline::bezier_ease::BezierEase;
-use std::fmt::Debug;
+use crate::{
+    spline::bezier_ease::BezierEase,
+    spring::{unit_motion, unit_settle_time},
+};
+use serde::{
+    de::{
+        self, value::MapAccessDeserializer, DeserializeSeed, Error as _, IntoDeserializer,
+        MapAccess, Visitor,
+    },
+    ser::Error as _,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{
+    convert::TryFrom,
+    fmt::{self, Debug},
+    sync::Arc,
+};
 
 pub type EaseFunction = fn(f64) -> f64;
 
-#[derive(Clone, Copy)]
+/// An ease that can capture state, such as a lookup table built at runtime.
+pub type CustomEase = Arc<dyn Fn(f64) -> f64 + Send + Sync>;
+
+/// One of the built-in eases, which all have known derivatives.
+///
+/// Presets are serialized by the name of their constructor on [`Ease`], i.e.
+/// `"out_bounce"`.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Preset {
+    InSine,
+    OutSine,
+    InOutSine,
+    InQuad,
+    OutQuad,
+    InOutQuad,
+    InCubic,
+    OutCubic,
+    InOutCubic,
+    InQuart,
+    OutQuart,
+    InOutQuart,
+    InQuint,
+    OutQuint,
+    InOutQuint,
+    InExpo,
+    OutExpo,
+    InOutExpo,
+    InCircle,
+    OutCircle,
+    InOutCircle,
+    InBack,
+    OutBack,
+    InOutBack,
+    InElastic,
+    OutElastic,
+    InOutElastic,
+    InBounce,
+    OutBounce,
+    InOutBounce,
+    Linear,
+    Lazy,
+}
+
+impl Preset {
+    pub const ALL: [Self; 32] = [
+        Self::InSine,
+        Self::OutSine,
+        Self::InOutSine,
+        Self::InQuad,
+        Self::OutQuad,
+        Self::InOutQuad,
+        Self::InCubic,
+        Self::OutCubic,
+        Self::InOutCubic,
+        Self::InQuart,
+        Self::OutQuart,
+        Self::InOutQuart,
+        Self::InQuint,
+        Self::OutQuint,
+        Self::InOutQuint,
+        Self::InExpo,
+        Self::OutExpo,
+        Self::InOutExpo,
+        Self::InCircle,
+        Self::OutCircle,
+        Self::InOutCircle,
+        Self::InBack,
+        Self::OutBack,
+        Self::InOutBack,
+        Self::InElastic,
+        Self::OutElastic,
+        Self::InOutElastic,
+        Self::InBounce,
+        Self::OutBounce,
+        Self::InOutBounce,
+        Self::Linear,
+        Self::Lazy,
+    ];
+
+    pub fn ease(&self, t: f64) -> f64 {
+        match self {
+            Self::InSine => in_sine(t),
+            Self::OutSine => out_sine(t),
+            Self::InOutSine => in_out_sine(t),
+            Self::InQuad => in_quad(t),
+            Self::OutQuad => out_quad(t),
+            Self::InOutQuad => in_out_quad(t),
+            Self::InCubic => in_cubic(t),
+            Self::OutCubic => out_cubic(t),
+            Self::InOutCubic => in_out_cubic(t),
+            Self::InQuart => in_quart(t),
+            Self::OutQuart => out_quart(t),
+            Self::InOutQuart => in_out_quart(t),
+            Self::InQuint => in_quint(t),
+            Self::OutQuint => out_quint(t),
+            Self::InOutQuint => in_out_quint(t),
+            Self::InExpo => in_expo(t),
+            Self::OutExpo => out_expo(t),
+            Self::InOutExpo => in_out_expo(t),
+            Self::InCircle => in_circle(t),
+            Self::OutCircle => out_circle(t),
+            Self::InOutCircle => in_out_circle(t),
+            Self::InBack => in_back(t),
+            Self::OutBack => out_back(t),
+            Self::InOutBack => in_out_back(t),
+            Self::InElastic => in_elastic(t),
+            Self::OutElastic => out_elastic(t),
+            Self::InOutElastic => in_out_elastic(t),
+            Self::InBounce => in_bounce(t),
+            Self::OutBounce => out_bounce(t),
+            Self::InOutBounce => in_out_bounce(t),
+            Self::Linear => identity(t),
+            Self::Lazy => lazy(t),
+        }
+    }
+
+    /// The slope of the ease at `t`.
+    pub fn derivative(&self, t: f64) -> f64 {
+        match self {
+            Self::InSine => dt_in_sine(t),
+            Self::OutSine => dt_out_sine(t),
+            Self::InOutSine => dt_in_out_sine(t),
+            Self::InQuad => dt_in_quad(t),
+            Self::OutQuad => dt_out_quad(t),
+            Self::InOutQuad => dt_in_out_quad(t),
+            Self::InCubic => dt_in_cubic(t),
+            Self::OutCubic => dt_out_cubic(t),
+            Self::InOutCubic => dt_in_out_cubic(t),
+            Self::InQuart => dt_in_quart(t),
+            Self::OutQuart => dt_out_quart(t),
+            Self::InOutQuart => dt_in_out_quart(t),
+            Self::InQuint => dt_in_quint(t),
+            Self::OutQuint => dt_out_quint(t),
+            Self::InOutQuint => dt_in_out_quint(t),
+            Self::InExpo => dt_in_expo(t),
+            Self::OutExpo => dt_out_expo(t),
+            Self::InOutExpo => dt_in_out_expo(t),
+            Self::InCircle => dt_in_circle(t),
+            Self::OutCircle => dt_out_circle(t),
+            Self::InOutCircle => dt_in_out_circle(t),
+            Self::InBack => dt_in_back(t),
+            Self::OutBack => dt_out_back(t),
+            Self::InOutBack => dt_in_out_back(t),
+            Self::InElastic => dt_in_elastic(t),
+            Self::OutElastic => dt_out_elastic(t),
+            Self::InOutElastic => dt_in_out_elastic(t),
+            Self::InBounce => dt_in_bounce(t),
+            Self::OutBounce => dt_out_bounce(t),
+            Self::InOutBounce => dt_in_out_bounce(t),
+            Self::Linear => dt_identity(t),
+            Self::Lazy => dt_lazy(t),
+        }
+    }
+}
+
+/// Which end of the animation a [`EaseFamily`] is applied to.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EaseMode {
+    In,
+    /// The time-reversal of `In`, i.e. `1 - f(1 - t)`.
+    Out,
+    /// `In` for the first half, and `Out` for the second.
+    InOut,
+}
+
+impl EaseMode {
+    fn apply(self, f: impl Fn(f64) -> f64, t: f64) -> f64 {
+        match self {
+            Self::In => f(t),
+            Self::Out => 1.0 - f(1.0 - t),
+            Self::InOut => {
+                if t < 0.5 {
+                    f(2.0 * t) / 2.0
+                } else {
+                    1.0 - f(2.0 - 2.0 * t) / 2.0
+                }
+            }
+        }
+    }
+
+    fn apply_derivative(self, dt: impl Fn(f64) -> f64, t: f64) -> f64 {
+        match self {
+            Self::In => dt(t),
+            Self::Out => dt(1.0 - t),
+            Self::InOut => {
+                if t < 0.5 {
+                    dt(2.0 * t)
+                } else {
+                    dt(2.0 - 2.0 * t)
+                }
+            }
+        }
+    }
+}
+
+/// Where the jumps of [`EaseFamily::steps`] happen, matching CSS's
+/// `steps()` timing function.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JumpMode {
+    /// Jumps at the start of each step, so the first step is already past `0`.
+    Start,
+    /// Jumps at the end of each step, so the last step holds until `1`.
+    End,
+    /// Holds both `0` and `1` for a step, jumping only in between.
+    None,
+    /// Jumps at both ends, so neither `0` nor `1` are held.
+    Both,
+}
+
+/// An ease with tunable parameters, given in its `In` form.
+///
+/// Use the constructors to create these, which validate the parameters.
+/// Combined with an [`EaseMode`] in [`Ease::Parametric`].
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(into = "EaseFamilyRepr", try_from = "EaseFamilyRepr")]
+pub struct EaseFamily(pub(crate) FamilyKind);
+
+// The parameters are kept private so that they can only be set through the
+// validating constructors, which also fill in any derived parameters
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum FamilyKind {
+    Power {
+        exponent: f64,
+    },
+    Back {
+        overshoot: f64,
+    },
+    Elastic {
+        amplitude: f64,
+        period: f64,
+    },
+    Bounce {
+        bounces: u32,
+        restitution: f64,
+    },
+    Steps {
+        steps: u32,
+        jump: JumpMode,
+    },
+    Exponential {
+        strength: f64,
+    },
+    Spring {
+        damping_ratio: f64,
+        // The settle time of the spring, which is normalized to 1
+        settle: f64,
+    },
+}
+
+// `EaseFamily` without its derived parameters, so deserialization can go
+// through validation
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum EaseFamilyRepr {
+    Power { exponent: f64 },
+    Back { overshoot: f64 },
+    Elastic { amplitude: f64, period: f64 },
+    Bounce { bounces: u32, restitution: f64 },
+    Steps { steps: u32, jump: JumpMode },
+    Exponential { strength: f64 },
+    Spring { damping_ratio: f64 },
+}
+
+impl From<EaseFamily> for EaseFamilyRepr {
+    fn from(family: EaseFamily) -> Self {
+        match family.0 {
+            FamilyKind::Power { exponent } => Self::Power { exponent },
+            FamilyKind::Back { overshoot } => Self::Back { overshoot },
+            FamilyKind::Elastic { amplitude, period } => Self::Elastic { amplitude, period },
+            FamilyKind::Bounce {
+                bounces,
+                restitution,
+            } => Self::Bounce {
+                bounces,
+                restitution,
+            },
+            FamilyKind::Steps { steps, jump } => Self::Steps { steps, jump },
+            FamilyKind::Exponential { strength } => Self::Exponential { strength },
+            FamilyKind::Spring { damping_ratio, .. } => Self::Spring { damping_ratio },
+        }
+    }
+}
+
+impl TryFrom<EaseFamilyRepr> for EaseFamily {
+    type Error = String;
+
+    fn try_from(repr: EaseFamilyRepr) -> Result<Self, Self::Error> {
+        match repr {
+            EaseFamilyRepr::Power { exponent } => Self::try_power(exponent),
+            EaseFamilyRepr::Back { overshoot } => Self::try_back(overshoot),
+            EaseFamilyRepr::Elastic { amplitude, period } => Self::try_elastic(amplitude, period),
+            EaseFamilyRepr::Bounce {
+                bounces,
+                restitution,
+            } => Self::try_bounce(bounces, restitution),
+            EaseFamilyRepr::Steps { steps, jump } => Self::try_steps(steps, jump),
+            EaseFamilyRepr::Exponential { strength } => Self::try_exponential(strength),
+            EaseFamilyRepr::Spring { damping_ratio } => Self::try_spring(damping_ratio),
+        }
+    }
+}
+
+fn check(valid: bool, message: impl FnOnce() -> String) -> Result<(), String> {
+    if valid {
+        Ok(())
+    } else {
+        Err(message())
+    }
+}
+
+fn unwrap_family(family: Result<EaseFamily, String>) -> EaseFamily {
+    family.unwrap_or_else(|err| panic!("{}", err))
+}
+
+impl EaseFamily {
+    /// `t^exponent`.
+    ///
+    /// # Panics
+    /// If `exponent` isn't positive and finite.
+    pub fn power(exponent: f64) -> Self {
+        unwrap_family(Self::try_power(exponent))
+    }
+
+    /// Pulls back by an amount controlled by `overshoot` before taking off.
+    /// The classic back ease uses an overshoot of `1.70158`.
+    ///
+    /// # Panics
+    /// If `overshoot` isn't non-negative and finite.
+    pub fn back(overshoot: f64) -> Self {
+        unwrap_family(Self::try_back(overshoot))
+    }
+
+    /// Oscillates with a period of `period` (as a fraction of the ease's
+    /// duration), swinging out to `amplitude` at most. The classic elastic ease
+    /// uses an amplitude of `1.0` and a period of `0.3`.
+    ///
+    /// # Panics
+    /// If `amplitude` isn't at least `1.0`, or `period` isn't positive.
+    pub fn elastic(amplitude: f64, period: f64) -> Self {
+        unwrap_family(Self::try_elastic(amplitude, period))
+    }
+
+    /// Bounces `bounces` times, losing speed on each bounce by a factor of
+    /// `restitution`. Bounces are physically accurate, so the ease falls with
+    /// constant acceleration.
+    ///
+    /// # Panics
+    /// If `bounces` is zero, or `restitution` isn't between `0.0` and `1.0`.
+    pub fn bounce(bounces: u32, restitution: f64) -> Self {
+        unwrap_family(Self::try_bounce(bounces, restitution))
+    }
+
+    /// Jumps between `steps` discrete values, like CSS's `steps()`.
+    ///
+    /// # Panics
+    /// If `steps` is zero, or is one with [`JumpMode::None`].
+    pub fn steps(steps: u32, jump: JumpMode) -> Self {
+        unwrap_family(Self::try_steps(steps, jump))
+    }
+
+    /// `(2^(strength * t) - 1) / (2^strength - 1)`, which eases in for a
+    /// positive `strength`, and out for a negative one.
+    ///
+    /// # Panics
+    /// If `strength` is zero or isn't finite.
+    pub fn exponential(strength: f64) -> Self {
+        unwrap_family(Self::try_exponential(strength))
+    }
+
+    /// The motion of a spring released from rest, normalized so that it's
+    /// settled by the end of the ease. See [`crate::SpringConfig`] for the
+    /// meaning of `damping_ratio`. Springs are natural in their `Out` form.
+    ///
+    /// # Panics
+    /// If `damping_ratio` isn't positive and finite.
+    pub fn spring(damping_ratio: f64) -> Self {
+        unwrap_family(Self::try_spring(damping_ratio))
+    }
+
+    fn try_power(exponent: f64) -> Result<Self, String> {
+        check(exponent.is_finite() && exponent > 0.0, || {
+            format!(
+                "power ease exponent must be positive and finite, but you specified {}",
+                exponent
+            )
+        })?;
+        Ok(Self(FamilyKind::Power { exponent }))
+    }
+
+    fn try_back(overshoot: f64) -> Result<Self, String> {
+        check(overshoot.is_finite() && overshoot >= 0.0, || {
+            format!(
+                "back ease overshoot must be non-negative and finite, but you specified {}",
+                overshoot
+            )
+        })?;
+        Ok(Self(FamilyKind::Back { overshoot }))
+    }
+
+    fn try_elastic(amplitude: f64, period: f64) -> Result<Self, String> {
+        check(amplitude.is_finite() && amplitude >= 1.0, || {
+            format!(
+                "elastic ease amplitude must be at least 1 and finite, but you specified {}",
+                amplitude
+            )
+        })?;
+        check(period.is_finite() && period > 0.0, || {
+            format!(
+                "elastic ease period must be positive and finite, but you specified {}",
+                period
+            )
+        })?;
+        Ok(Self(FamilyKind::Elastic { amplitude, period }))
+    }
+
+    fn try_bounce(bounces: u32, restitution: f64) -> Result<Self, String> {
+        check(bounces > 0, || {
+            "bounce ease must bounce at least once".to_owned()
+        })?;
+        check(restitution > 0.0 && restitution < 1.0, || {
+            format!(
+                "bounce ease restitution must be between 0 and 1, but you specified {}",
+                restitution
+            )
+        })?;
+        Ok(Self(FamilyKind::Bounce {
+            bounces,
+            restitution,
+        }))
+    }
+
+    pub(crate) fn try_steps(steps: u32, jump: JumpMode) -> Result<Self, String> {
+        let min = if jump == JumpMode::None { 2 } else { 1 };
+        check(steps >= min, || {
+            format!(
+                "{:?} steps ease requires at least {} steps, but you specified {}",
+                jump, min, steps
+            )
+        })?;
+        Ok(Self(FamilyKind::Steps { steps, jump }))
+    }
+
+    fn try_exponential(strength: f64) -> Result<Self, String> {
+        check(strength.is_finite() && strength != 0.0, || {
+            format!(
+                "exponential ease strength must be nonzero and finite, but you specified {}",
+                strength
+            )
+        })?;
+        Ok(Self(FamilyKind::Exponential { strength }))
+    }
+
+    fn try_spring(damping_ratio: f64) -> Result<Self, String> {
+        check(damping_ratio.is_finite() && damping_ratio > 0.0, || {
+            format!(
+                "spring ease damping ratio must be positive and finite, but you specified {}",
+                damping_ratio
+            )
+        })?;
+        Ok(Self(FamilyKind::Spring {
+            damping_ratio,
+            settle: unit_settle_time(damping_ratio),
+        }))
+    }
+
+    /// The `In` form of the ease at `t`.
+    pub fn ease(&self, t: f64) -> f64 {
+        match self.0 {
+            FamilyKind::Power { exponent } => t.powf(exponent),
+            FamilyKind::Back { overshoot } => in_back_with(t, overshoot),
+            FamilyKind::Elastic { amplitude, period } => in_elastic_with(t, amplitude, period),
+            FamilyKind::Bounce {
+                bounces,
+                restitution,
+            } => 1.0 - out_bounce_with(1.0 - t, bounces, restitution),
+            FamilyKind::Steps { steps, jump } => steps_with(t, steps, jump),
+            FamilyKind::Exponential { strength } => in_exponential_with(t, strength),
+            FamilyKind::Spring {
+                damping_ratio,
+                settle,
+            } => 1.0 - out_spring_with(1.0 - t, damping_ratio, settle),
+        }
+    }
+
+    /// The slope of the `In` form of the ease at `t`.
+    pub fn derivative(&self, t: f64) -> f64 {
+        match self.0 {
+            FamilyKind::Power { exponent } => exponent * t.powf(exponent - 1.0),
+            FamilyKind::Back { overshoot } => dt_in_back_with(t, overshoot),
+            FamilyKind::Elastic { amplitude, period } => dt_in_elastic_with(t, amplitude, period),
+            FamilyKind::Bounce {
+                bounces,
+                restitution,
+            } => dt_out_bounce_with(1.0 - t, bounces, restitution),
+            FamilyKind::Steps { .. } => 0.0,
+            FamilyKind::Exponential { strength } => dt_in_exponential_with(t, strength),
+            FamilyKind::Spring {
+                damping_ratio,
+                settle,
+            } => dt_out_spring_with(1.0 - t, damping_ratio, settle),
+        }
+    }
+}
+
+/// Linear interpolation between `(t, value)` points, like CSS's `linear()`.
+///
+/// Create these with [`Ease::piecewise`], which validates the points.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PiecewiseEase(Arc<[(f64, f64)]>);
+
+impl PiecewiseEase {
+    fn try_new(points: Vec<(f64, f64)>) -> Result<Self, String> {
+        check(points.len() >= 2, || {
+            format!(
+                "piecewise ease requires at least 2 points, but you specified {}",
+                points.len()
+            )
+        })?;
+        check(
+            points
+                .iter()
+                .all(|(t, value)| t.is_finite() && value.is_finite()),
+            || "piecewise ease points must be finite".to_owned(),
+        )?;
+        check(points.windows(2).all(|pair| pair[0].0 <= pair[1].0), || {
+            "piecewise ease points must be sorted by `t`".to_owned()
+        })?;
+        Ok(Self(points.into()))
+    }
+
+    /// The `(t, value)` points, sorted by `t`.
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.0
+    }
+
+    pub fn ease(&self, t: f64) -> f64 {
+        let ((x0, y0), (x1, y1)) = self.segment(t);
+        if x1 == x0 {
+            y1
+        } else {
+            y0 + (y1 - y0) * (t - x0) / (x1 - x0)
+        }
+    }
+
+    pub fn derivative(&self, t: f64) -> f64 {
+        let ((x0, y0), (x1, y1)) = self.segment(t);
+        if x1 == x0 {
+            0.0
+        } else {
+            (y1 - y0) / (x1 - x0)
+        }
+    }
+
+    // The segment to interpolate along at `t`, where the first and last
+    // segments extend outwards
+    fn segment(&self, t: f64) -> ((f64, f64), (f64, f64)) {
+        let index = self
+            .0
+            .partition_point(|(x, _)| *x <= t)
+            .clamp(1, self.0.len() - 1);
+        (self.0[index - 1], self.0[index])
+    }
+}
+
+/// An ease built out of other eases using the combinators on [`Ease`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(into = "CompositeKind", try_from = "CompositeKind")]
+pub struct Composite(CompositeKind);
+
+// Kept private so that composites can only be made through the combinators,
+// which validate their parameters
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum CompositeKind {
+    Reverse(Ease),
+    Mirror(Ease),
+    Chain {
+        first: Ease,
+        second: Ease,
+        split: f64,
+    },
+    ScaleOvershoot {
+        ease: Ease,
+        factor: f64,
+    },
+    Mix {
+        a: Ease,
+        b: Ease,
+        weight: f64,
+    },
+}
+
+impl From<Composite> for CompositeKind {
+    fn from(composite: Composite) -> Self {
+        composite.0
+    }
+}
+
+impl TryFrom<CompositeKind> for Composite {
+    type Error = String;
+
+    fn try_from(kind: CompositeKind) -> Result<Self, Self::Error> {
+        match kind {
+            CompositeKind::Reverse(_) | CompositeKind::Mirror(_) => Ok(()),
+            CompositeKind::Chain { split, .. } => check(split > 0.0 && split < 1.0, || {
+                format!(
+                    "chained ease split must be between 0 and 1, but you specified {}",
+                    split
+                )
+            }),
+            CompositeKind::ScaleOvershoot { factor, .. } => {
+                check(factor.is_finite() && factor >= 0.0, || {
+                    format!(
+                        "overshoot scale must be non-negative and finite, but you specified {}",
+                        factor
+                    )
+                })
+            }
+            CompositeKind::Mix { weight, .. } => check(weight.is_finite(), || {
+                format!(
+                    "ease mix weight must be finite, but you specified {}",
+                    weight
+                )
+            }),
+        }?;
+        Ok(Self(kind))
+    }
+}
+
+impl Composite {
+    pub fn ease(&self, t: f64) -> f64 {
+        match &self.0 {
+            CompositeKind::Reverse(ease) => EaseMode::Out.apply(|t| ease.ease(t), t),
+            CompositeKind::Mirror(ease) => EaseMode::InOut.apply(|t| ease.ease(t), t),
+            CompositeKind::Chain {
+                first,
+                second,
+                split,
+            } => {
+                if t < *split {
+                    split * first.ease(t / split)
+                } else {
+                    split + (1.0 - split) * second.ease((t - split) / (1.0 - split))
+                }
+            }
+            CompositeKind::ScaleOvershoot { ease, factor } => {
+                let value = ease.ease(t);
+                if value > 1.0 {
+                    1.0 + (value - 1.0) * factor
+                } else if value < 0.0 {
+                    value * factor
+                } else {
+                    value
+                }
+            }
+            CompositeKind::Mix { a, b, weight } => a.ease(t) + (b.ease(t) - a.ease(t)) * weight,
+        }
+    }
+
+    /// The slope of the ease at `t`, if the slopes of all of the eases it's
+    /// made of are known.
+    pub fn derivative(&self, t: f64) -> Option<f64> {
+        match &self.0 {
+            CompositeKind::Reverse(ease) => ease.derivative(1.0 - t),
+            CompositeKind::Mirror(ease) => {
+                if t < 0.5 {
+                    ease.derivative(2.0 * t)
+                } else {
+                    ease.derivative(2.0 - 2.0 * t)
+                }
+            }
+            CompositeKind::Chain {
+                first,
+                second,
+                split,
+            } => {
+                if t < *split {
+                    first.derivative(t / split)
+                } else {
+                    second.derivative((t - split) / (1.0 - split))
+                }
+            }
+            CompositeKind::ScaleOvershoot { ease, factor } => {
+                let value = ease.ease(t);
+                let derivative = ease.derivative(t)?;
+                if (0.0..=1.0).contains(&value) {
+                    Some(derivative)
+                } else {
+                    Some(derivative * factor)
+                }
+            }
+            CompositeKind::Mix { a, b, weight } => {
+                let (a, b) = (a.derivative(t)?, b.derivative(t)?);
+                Some(a + (b - a) * weight)
+            }
+        }
+    }
+}
+
+/// How an animation progresses from start to end.
+///
+/// `Ease` is `Clone` but not `Copy`, since piecewise, composite, and closure
+/// eases hold shared data. Cloning only bumps a reference count, so replace
+/// copies like `*ease` with `ease.clone()`. [`BezierEase`], [`Preset`], and
+/// [`EaseFamily`] are still `Copy`.
+#[derive(Clone)]
 pub enum Ease {
     Bezier(BezierEase),
+    Preset(Preset),
+    /// An ease with tunable parameters, applied in the given mode.
+    Parametric(EaseFamily, EaseMode),
+    /// Linear interpolation between points, like CSS's `linear()`.
+    Piecewise(PiecewiseEase),
+    Composite(Arc<Composite>),
     Function(EaseFunction),
+    /// An ease closure, which is shared between clones of the ease.
+    Custom(CustomEase),
+    /// An ease function along with its derivative.
+    Differentiable {
+        ease: EaseFunction,
+        derivative: EaseFunction,
+    },
 }
 
 impl Debug for Ease {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Ease::Bezier(ease) => write!(f, "Ease::Bezier({:?})", ease),
+            Ease::Preset(preset) => write!(f, "Ease::Preset({:?})", preset),
+            Ease::Parametric(family, mode) => {
+                write!(f, "Ease::Parametric({:?}, {:?})", family, mode)
+            }
+            Ease::Piecewise(piecewise) => {
+                write!(f, "Ease::Piecewise({:?})", piecewise.points())
+            }
+            Ease::Composite(composite) => write!(f, "Ease::Composite({:?})", composite),
             Ease::Function(_) => write!(f, "Ease::Function(...)"),
+            Ease::Custom(_) => write!(f, "Ease::Custom(...)"),
+            Ease::Differentiable { .. } => write!(f, "Ease::Differentiable(...)"),
+        }
+    }
+}
+
+// How an `Ease` is represented when serialized: presets are named, and Bezier
+// eases are given by their control points
+#[derive(Serialize)]
+#[serde(untagged)]
+enum EaseRepr {
+    Preset(Preset),
+    Bezier(BezierEase),
+    Parametric(ParametricRepr),
+    Piecewise(PiecewiseRepr),
+    Composite(Composite),
+}
+
+#[derive(Deserialize, Serialize)]
+struct ParametricRepr {
+    family: EaseFamily,
+    mode: EaseMode,
+}
+
+#[derive(Deserialize, Serialize)]
+struct PiecewiseRepr {
+    points: Vec<(f64, f64)>,
+}
+
+/// Eases built from functions can't be serialized, since there's no way to
+/// name them.
+impl Serialize for Ease {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Ease::Bezier(bezier) => EaseRepr::Bezier(*bezier).serialize(serializer),
+            Ease::Preset(preset) => EaseRepr::Preset(*preset).serialize(serializer),
+            Ease::Parametric(family, mode) => EaseRepr::Parametric(ParametricRepr {
+                family: *family,
+                mode: *mode,
+            })
+            .serialize(serializer),
+            Ease::Piecewise(piecewise) => EaseRepr::Piecewise(PiecewiseRepr {
+                points: piecewise.points().to_vec(),
+            })
+            .serialize(serializer),
+            Ease::Composite(composite) => {
+                EaseRepr::Composite(Composite::clone(composite)).serialize(serializer)
+            }
+            Ease::Function(_) | Ease::Custom(_) | Ease::Differentiable { .. } => Err(
+                S::Error::custom("eases built from functions can't be serialized"),
+            ),
+        }
+    }
+}
+
+// Picks the representation from the first key of a map rather than trying
+// each one in turn, so that errors from the chosen representation (like an
+// invalid parameter) reach the caller instead of a generic "no variant
+// matched"
+struct EaseVisitor;
+
+impl<'de> Visitor<'de> for EaseVisitor {
+    type Value = Ease;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a preset name, Bezier control points, or an ease description")
+    }
+
+    fn visit_str<E: de::Error>(self, name: &str) -> Result<Self::Value, E> {
+        Preset::deserialize(name.into_deserializer()).map(Ease::Preset)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let first = map
+            .next_key::<String>()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        let kind = first.clone();
+        let map = MapAccessDeserializer::new(ReplayFirstKey {
+            first: Some(first),
+            map,
+        });
+        match kind.as_str() {
+            "ox" | "oy" | "ix" | "iy" => BezierEase::deserialize(map).map(Ease::Bezier),
+            "family" | "mode" => ParametricRepr::deserialize(map)
+                .map(|ParametricRepr { family, mode }| Ease::Parametric(family, mode)),
+            "points" => PiecewiseRepr::deserialize(map).and_then(|PiecewiseRepr { points }| {
+                Ease::try_piecewise(points).map_err(A::Error::custom)
+            }),
+            _ => Composite::deserialize(map).map(|composite| Ease::Composite(Arc::new(composite))),
+        }
+    }
+}
+
+// Hands back a key that's already been read before continuing with the rest
+// of the map
+struct ReplayFirstKey<A> {
+    first: Option<String>,
+    map: A,
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for ReplayFirstKey<A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        match self.first.take() {
+            Some(first) => seed.deserialize(first.into_deserializer()).map(Some),
+            None => self.map.next_key_seed(seed),
         }
     }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.map.next_value_seed(seed)
+    }
+}
+
+impl<'de> Deserialize<'de> for Ease {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(EaseVisitor)
+    }
+}
+
+impl From<Preset> for Ease {
+    fn from(preset: Preset) -> Self {
+        Self::Preset(preset)
+    }
 }
 
 impl Ease {
     pub fn ease(&self, t: f64) -> f64 {
         match self {
             Ease::Bezier(bezier) => bezier.ease(t),
+            Ease::Preset(preset) => preset.ease(t),
+            Ease::Parametric(family, mode) => mode.apply(|t| family.ease(t), t),
+            Ease::Piecewise(piecewise) => piecewise.ease(t),
+            Ease::Composite(composite) => composite.ease(t),
             Ease::Function(ease) => ease(t),
+            Ease::Custom(ease) => ease(t),
+            Ease::Differentiable { ease, .. } => ease(t),
         }
     }
 
+    /// The slope of the ease at `t`, if it's known analytically.
+    ///
+    /// This is `None` for [`Ease::Function`] and [`Ease::Custom`]; use
+    /// [`Ease::with_derivative`] to provide a derivative for your own ease
+    /// functions.
+    pub fn derivative(&self, t: f64) -> Option<f64> {
+        match self {
+            Ease::Bezier(bezier) => Some(bezier.derivative(t)),
+            Ease::Preset(preset) => Some(preset.derivative(t)),
+            Ease::Parametric(family, mode) => {
+                Some(mode.apply_derivative(|t| family.derivative(t), t))
+            }
+            Ease::Piecewise(piecewise) => Some(piecewise.derivative(t)),
+            Ease::Composite(composite) => composite.derivative(t),
+            Ease::Function(_) | Ease::Custom(_) => None,
+            Ease::Differentiable { derivative, .. } => Some(derivative(t)),
+        }
+    }
+
+    pub fn with_derivative(ease: EaseFunction, derivative: EaseFunction) -> Self {
+        Self::Differentiable { ease, derivative }
+    }
+
+    pub fn custom(ease: impl Fn(f64) -> f64 + Send + Sync + 'static) -> Self {
+        Self::Custom(Arc::new(ease))
+    }
+
+    fn composite(kind: CompositeKind) -> Self {
+        let composite = Composite::try_from(kind).unwrap_or_else(|err| panic!("{}", err));
+        Self::Composite(Arc::new(composite))
+    }
+
+    /// The time-reversal of this ease, i.e. `1 - f(1 - t)`, which turns an
+    /// ease in into an ease out and vice versa.
+    pub fn reverse(self) -> Self {
+        match self {
+            Self::Parametric(family, EaseMode::In) => Self::Parametric(family, EaseMode::Out),
+            Self::Parametric(family, EaseMode::Out) => Self::Parametric(family, EaseMode::In),
+            // In-out eases are their own reverse
+            Self::Parametric(_, EaseMode::InOut) => self,
+            _ => Self::composite(CompositeKind::Reverse(self)),
+        }
+    }
+
+    /// Plays this ease over the first half, and its reverse over the second,
+    /// which turns an ease in into an ease in-out.
+    pub fn mirror(self) -> Self {
+        match self {
+            Self::Parametric(family, EaseMode::In) => Self::Parametric(family, EaseMode::InOut),
+            _ => Self::composite(CompositeKind::Mirror(self)),
+        }
+    }
+
+    /// Plays this ease until `split`, and then `other` for the rest. Both eases
+    /// are scaled to fit, so the result passes through `(split, split)`.
+    ///
+    /// # Panics
+    /// If `split` isn't between `0.0` and `1.0`.
+    pub fn chain(self, other: Self, split: f64) -> Self {
+        Self::composite(CompositeKind::Chain {
+            first: self,
+            second: other,
+            split,
+        })
+    }
+
+    /// Multiplies how far this ease goes outside of `0.0..=1.0` by `factor`,
+    /// so a factor of `0.5` halves the overshoot of a back ease, and `0.0`
+    /// removes it entirely.
+    ///
+    /// # Panics
+    /// If `factor` isn't non-negative and finite.
+    pub fn scale_overshoot(self, factor: f64) -> Self {
+        Self::composite(CompositeKind::ScaleOvershoot { ease: self, factor })
+    }
+
+    /// Linearly interpolates between this ease and `other`, where a `weight`
+    /// of `0.0` is entirely this ease, and `1.0` is entirely `other`.
+    ///
+    /// # Panics
+    /// If `weight` isn't finite.
+    pub fn mix(self, other: Self, weight: f64) -> Self {
+        Self::composite(CompositeKind::Mix {
+            a: self,
+            b: other,
+            weight,
+        })
+    }
+
+    /// Linearly interpolates between `(t, value)` points. Before the first
+    /// point and after the last, the first and last segments are extended.
+    ///
+    /// # Panics
+    /// If there are fewer than 2 points, any of them aren't finite, or they
+    /// aren't sorted by `t`.
+    pub fn piecewise(points: impl IntoIterator<Item = (f64, f64)>) -> Self {
+        Self::try_piecewise(points.into_iter().collect()).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub(crate) fn try_piecewise(points: Vec<(f64, f64)>) -> Result<Self, String> {
+        PiecewiseEase::try_new(points).map(Self::Piecewise)
+    }
+
     pub fn in_sine() -> Self {
-        Self::Function(in_sine)
+        Self::Preset(Preset::InSine)
     }
     pub fn out_sine() -> Self {
-        Self::Function(out_sine)
+        Self::Preset(Preset::OutSine)
     }
     pub fn in_out_sine() -> Self {
-        Self::Function(in_out_sine)
+        Self::Preset(Preset::InOutSine)
     }
 
     pub fn in_quad() -> Self {
-        Self::Function(in_quad)
+        Self::Preset(Preset::InQuad)
     }
     pub fn out_quad() -> Self {
-        Self::Function(out_quad)
+        Self::Preset(Preset::OutQuad)
     }
     pub fn in_out_quad() -> Self {
-        Self::Function(in_out_quad)
+        Self::Preset(Preset::InOutQuad)
     }
 
     pub fn in_cubic() -> Self {
-        Self::Function(in_cubic)
+        Self::Preset(Preset::InCubic)
     }
     pub fn out_cubic() -> Self {
-        Self::Function(out_cubic)
+        Self::Preset(Preset::OutCubic)
     }
     pub fn in_out_cubic() -> Self {
-        Self::Function(in_out_cubic)
+        Self::Preset(Preset::InOutCubic)
     }
 
     pub fn in_quart() -> Self {
-        Self::Function(in_quart)
+        Self::Preset(Preset::InQuart)
     }
     pub fn out_quart() -> Self {
-        Self::Function(out_quart)
+        Self::Preset(Preset::OutQuart)
     }
     pub fn in_out_quart() -> Self {
-        Self::Function(in_out_quart)
+        Self::Preset(Preset::InOutQuart)
     }
 
     pub fn in_quint() -> Self {
-        Self::Function(in_quint)
+        Self::Preset(Preset::InQuint)
     }
     pub fn out_quint() -> Self {
-        Self::Function(out_quint)
+        Self::Preset(Preset::OutQuint)
     }
     pub fn in_out_quint() -> Self {
-        Self::Function(in_out_quint)
+        Self::Preset(Preset::InOutQuint)
     }
 
     pub fn in_expo() -> Self {
-        Self::Function(in_expo)
+        Self::Preset(Preset::InExpo)
     }
     pub fn out_expo() -> Self {
-        Self::Function(out_expo)
+        Self::Preset(Preset::OutExpo)
     }
     pub fn in_out_expo() -> Self {
-        Self::Function(in_out_expo)
+        Self::Preset(Preset::InOutExpo)
     }
 
     pub fn in_circle() -> Self {
-        Self::Function(in_circle)
+        Self::Preset(Preset::InCircle)
     }
     pub fn out_circle() -> Self {
-        Self::Function(out_circle)
+        Self::Preset(Preset::OutCircle)
     }
     pub fn in_out_circle() -> Self {
-        Self::Function(in_out_circle)
+        Self::Preset(Preset::InOutCircle)
     }
 
     pub fn in_back() -> Self {
-        Self::Function(in_back)
+        Self::Preset(Preset::InBack)
     }
     pub fn out_back() -> Self {
-        Self::Function(out_back)
+        Self::Preset(Preset::OutBack)
     }
     pub fn in_out_back() -> Self {
-        Self::Function(in_out_back)
+        Self::Preset(Preset::InOutBack)
     }
 
     pub fn in_elastic() -> Self {
-        Self::Function(in_elastic)
+        Self::Preset(Preset::InElastic)
     }
     pub fn out_elastic() -> Self {
-        Self::Function(out_elastic)
+        Self::Preset(Preset::OutElastic)
     }
     pub fn in_out_elastic() -> Self {
-        Self::Function(in_out_elastic)
+        Self::Preset(Preset::InOutElastic)
     }
 
     pub fn in_bounce() -> Self {
-        Self::Function(in_bounce)
+        Self::Preset(Preset::InBounce)
     }
     pub fn out_bounce() -> Self {
-        Self::Function(out_bounce)
+        Self::Preset(Preset::OutBounce)
     }
     pub fn in_out_bounce() -> Self {
-        Self::Function(in_out_bounce)
+        Self::Preset(Preset::InOutBounce)
+    }
+
+    /// See [`EaseFamily::power`].
+    pub fn power(mode: EaseMode, exponent: f64) -> Self {
+        Self::Parametric(EaseFamily::power(exponent), mode)
+    }
+    /// See [`EaseFamily::back`].
+    pub fn back(mode: EaseMode, overshoot: f64) -> Self {
+        Self::Parametric(EaseFamily::back(overshoot), mode)
+    }
+    /// See [`EaseFamily::elastic`].
+    pub fn elastic(mode: EaseMode, amplitude: f64, period: f64) -> Self {
+        Self::Parametric(EaseFamily::elastic(amplitude, period), mode)
+    }
+    /// See [`EaseFamily::bounce`].
+    pub fn bounce(mode: EaseMode, bounces: u32, restitution: f64) -> Self {
+        Self::Parametric(EaseFamily::bounce(bounces, restitution), mode)
+    }
+    /// See [`EaseFamily::steps`].
+    pub fn steps(steps: u32, jump: JumpMode) -> Self {
+        Self::Parametric(EaseFamily::steps(steps, jump), EaseMode::In)
+    }
+    /// See [`EaseFamily::exponential`].
+    pub fn exponential(mode: EaseMode, strength: f64) -> Self {
+        Self::Parametric(EaseFamily::exponential(strength), mode)
+    }
+    /// See [`EaseFamily::spring`].
+    pub fn spring(mode: EaseMode, damping_ratio: f64) -> Self {
+        Self::Parametric(EaseFamily::spring(damping_ratio), mode)
     }
 
     pub fn none() -> Self {
-        Self::Function(identity)
+        Self::Preset(Preset::Linear)
     }
     pub fn lazy() -> Self {
-        Self::Function(lazy)
+        Self::Preset(Preset::Lazy)
     }
 }
 
@@ -150,6 +1161,18 @@ fn in_out_sine(t: f64) -> f64 {
     -(f64::cos(t * std::f64::consts::PI) - 1.0) / 2.0
 }
 
+fn dt_in_sine(t: f64) -> f64 {
+    std::f64::consts::FRAC_PI_2 * f64::sin(t * std::f64::consts::FRAC_PI_2)
+}
+
+fn dt_out_sine(t: f64) -> f64 {
+    std::f64::consts::FRAC_PI_2 * f64::cos(t * std::f64::consts::FRAC_PI_2)
+}
+
+fn dt_in_out_sine(t: f64) -> f64 {
+    std::f64::consts::FRAC_PI_2 * f64::sin(t * std::f64::consts::PI)
+}
+
 // Exponential Eases ==========================================================
 
 fn in_exponential(t: f64, n: i32) -> f64 {
@@ -166,6 +1189,22 @@ fn in_out_exponential(t: f64, n: i32) -> f64 {
         .unwrap_or_else(|| 1.0 - (-2.0 * t + 2.0).powi(n) / 2.0)
 }
 
+fn dt_in_exponential(t: f64, n: i32) -> f64 {
+    f64::from(n) * t.powi(n - 1)
+}
+
+fn dt_out_exponential(t: f64, n: i32) -> f64 {
+    dt_in_exponential(1.0 - t, n)
+}
+
+fn dt_in_out_exponential(t: f64, n: i32) -> f64 {
+    if t < 0.5 {
+        f64::powi(2.0, n - 1) * dt_in_exponential(t, n)
+    } else {
+        f64::from(n) * (-2.0 * t + 2.0).powi(n - 1)
+    }
+}
+
 // Quad Eases =================================================================
 
 fn in_quad(t: f64) -> f64 {
@@ -180,6 +1219,18 @@ fn in_out_quad(t: f64) -> f64 {
     in_out_exponential(t, 2)
 }
 
+fn dt_in_quad(t: f64) -> f64 {
+    dt_in_exponential(t, 2)
+}
+
+fn dt_out_quad(t: f64) -> f64 {
+    dt_out_exponential(t, 2)
+}
+
+fn dt_in_out_quad(t: f64) -> f64 {
+    dt_in_out_exponential(t, 2)
+}
+
 // Cubic Eases ================================================================
 
 fn in_cubic(t: f64) -> f64 {
@@ -194,6 +1245,18 @@ fn in_out_cubic(t: f64) -> f64 {
     in_out_exponential(t, 3)
 }
 
+fn dt_in_cubic(t: f64) -> f64 {
+    dt_in_exponential(t, 3)
+}
+
+fn dt_out_cubic(t: f64) -> f64 {
+    dt_out_exponential(t, 3)
+}
+
+fn dt_in_out_cubic(t: f64) -> f64 {
+    dt_in_out_exponential(t, 3)
+}
+
 // Quart Eases ================================================================
 
 fn in_quart(t: f64) -> f64 {
@@ -208,6 +1271,18 @@ fn in_out_quart(t: f64) -> f64 {
     in_out_exponential(t, 4)
 }
 
+fn dt_in_quart(t: f64) -> f64 {
+    dt_in_exponential(t, 4)
+}
+
+fn dt_out_quart(t: f64) -> f64 {
+    dt_out_exponential(t, 4)
+}
+
+fn dt_in_out_quart(t: f64) -> f64 {
+    dt_in_out_exponential(t, 4)
+}
+
 // Quint Eases =================================================================
 
 fn in_quint(t: f64) -> f64 {
@@ -222,6 +1297,18 @@ fn in_out_quint(t: f64) -> f64 {
     in_out_exponential(t, 5)
 }
 
+fn dt_in_quint(t: f64) -> f64 {
+    dt_in_exponential(t, 5)
+}
+
+fn dt_out_quint(t: f64) -> f64 {
+    dt_out_exponential(t, 5)
+}
+
+fn dt_in_out_quint(t: f64) -> f64 {
+    dt_in_out_exponential(t, 5)
+}
+
 // Expo Eases =================================================================
 
 fn in_expo(t: f64) -> f64 {
@@ -242,6 +1329,22 @@ fn in_out_expo(t: f64) -> f64 {
     })
 }
 
+fn dt_in_expo(t: f64) -> f64 {
+    10.0 * std::f64::consts::LN_2 * 2.0_f64.powf(10.0 * t - 10.0)
+}
+
+fn dt_out_expo(t: f64) -> f64 {
+    -10.0 * std::f64::consts::LN_2 * 2.0_f64.powf(-10.0 * t)
+}
+
+fn dt_in_out_expo(t: f64) -> f64 {
+    if t < 0.5 {
+        10.0 * std::f64::consts::LN_2 * 2.0_f64.powf(20.0 * t - 10.0)
+    } else {
+        10.0 * std::f64::consts::LN_2 * 2.0_f64.powf(-20.0 * t + 10.0)
+    }
+}
+
 // Circle Eases ===============================================================
 
 fn in_circle(t: f64) -> f64 {
@@ -258,6 +1361,22 @@ fn in_out_circle(t: f64) -> f64 {
         .unwrap_or_else(|| ((1.0 - (-2.0 * t + 2.0).powi(2)).sqrt() + 1.0) / 2.0)
 }
 
+fn dt_in_circle(t: f64) -> f64 {
+    t / (1.0 - t.powi(2)).sqrt()
+}
+
+fn dt_out_circle(t: f64) -> f64 {
+    -dt_in_circle(t)
+}
+
+fn dt_in_out_circle(t: f64) -> f64 {
+    if t < 0.5 {
+        2.0 * t / (1.0 - (2.0 * t).powi(2)).sqrt()
+    } else {
+        (-2.0 * t + 2.0) / (1.0 - (-2.0 * t + 2.0).powi(2)).sqrt()
+    }
+}
+
 // Back Eases =================================================================
 
 fn in_back(t: f64) -> f64 {
@@ -278,6 +1397,25 @@ fn in_out_back(t: f64) -> f64 {
         })
 }
 
+fn dt_in_back(t: f64) -> f64 {
+    3.0 * 2.70158 * t.powi(2) - 2.0 * 1.70158 * t
+}
+
+fn dt_out_back(t: f64) -> f64 {
+    3.0 * 2.70158 * (t - 1.0).powi(2) + 2.0 * 1.70158 * (t - 1.0)
+}
+
+fn dt_in_out_back(t: f64) -> f64 {
+    let c = 2.5949095;
+
+    if t < 0.5 {
+        12.0 * (c + 1.0) * t.powi(2) - 4.0 * c * t
+    } else {
+        let u = 2.0 * t - 2.0;
+        3.0 * (c + 1.0) * u.powi(2) + 2.0 * c * u
+    }
+}
+
 // Elastic Eases ==============================================================
 
 fn in_elastic(t: f64) -> f64 {
@@ -305,6 +1443,34 @@ fn in_out_elastic(t: f64) -> f64 {
     })
 }
 
+fn dt_in_elastic(t: f64) -> f64 {
+    let c = std::f64::consts::TAU / 3.0;
+    let angle = (10.0 * t - 10.75) * c;
+    -f64::powf(2.0, 10.0 * t - 10.0)
+        * (10.0 * std::f64::consts::LN_2 * f64::sin(angle) + 10.0 * c * f64::cos(angle))
+}
+
+fn dt_out_elastic(t: f64) -> f64 {
+    let c = std::f64::consts::TAU / 3.0;
+    let angle = (10.0 * t - 0.75) * c;
+    -f64::powf(2.0, t * -10.0)
+        * (-10.0 * std::f64::consts::LN_2 * f64::sin(angle) + 10.0 * c * f64::cos(angle))
+}
+
+fn dt_in_out_elastic(t: f64) -> f64 {
+    let c = std::f64::consts::TAU / 4.5;
+    let angle = (20.0 * t - 11.125) * c;
+    if t < 0.5 {
+        -f64::powf(2.0, 20.0 * t - 10.0)
+            * (20.0 * std::f64::consts::LN_2 * f64::sin(angle) + 20.0 * c * f64::cos(angle))
+            / 2.0
+    } else {
+        f64::powf(2.0, -20.0 * t + 10.0)
+            * (-20.0 * std::f64::consts::LN_2 * f64::sin(angle) + 20.0 * c * f64::cos(angle))
+            / 2.0
+    }
+}
+
 // Bounce Eases ===============================================================
 
 fn in_bounce(t: f64) -> f64 {
@@ -332,6 +1498,161 @@ fn in_out_bounce(t: f64) -> f64 {
         .unwrap_or_else(|| (1.0 + out_bounce(2.0 * t - 1.0)) / 2.0)
 }
 
+fn dt_in_bounce(t: f64) -> f64 {
+    dt_out_bounce(1.0 - t)
+}
+
+fn dt_out_bounce(t: f64) -> f64 {
+    let n = 7.5625;
+    let d = 2.75;
+
+    if t < 1.0 / d {
+        2.0 * n * t
+    } else if t < 2.0 / d {
+        2.0 * n * (t - (1.5 / d))
+    } else if t < 2.5 / d {
+        2.0 * n * (t - (2.25 / d))
+    } else {
+        2.0 * n * (t - (2.625 / d))
+    }
+}
+
+fn dt_in_out_bounce(t: f64) -> f64 {
+    if t < 0.5 {
+        dt_out_bounce(1.0 - 2.0 * t)
+    } else {
+        dt_out_bounce(2.0 * t - 1.0)
+    }
+}
+
+// Parametric Eases ===========================================================
+
+fn in_back_with(t: f64, overshoot: f64) -> f64 {
+    (overshoot + 1.0) * t.powi(3) - overshoot * t.powi(2)
+}
+
+fn dt_in_back_with(t: f64, overshoot: f64) -> f64 {
+    3.0 * (overshoot + 1.0) * t.powi(2) - 2.0 * overshoot * t
+}
+
+// The phase offset at which an elastic ease with the given amplitude starts
+fn elastic_phase(amplitude: f64, period: f64) -> f64 {
+    period / std::f64::consts::TAU * (1.0 / amplitude).asin()
+}
+
+fn in_elastic_with(t: f64, amplitude: f64, period: f64) -> f64 {
+    if t <= 0.0 || t >= 1.0 {
+        t.clamp(0.0, 1.0)
+    } else {
+        let angle = (t - 1.0 - elastic_phase(amplitude, period)) * std::f64::consts::TAU / period;
+        -amplitude * f64::powf(2.0, 10.0 * (t - 1.0)) * angle.sin()
+    }
+}
+
+fn dt_in_elastic_with(t: f64, amplitude: f64, period: f64) -> f64 {
+    let c = std::f64::consts::TAU / period;
+    let angle = (t - 1.0 - elastic_phase(amplitude, period)) * c;
+    -amplitude
+        * f64::powf(2.0, 10.0 * (t - 1.0))
+        * (10.0 * std::f64::consts::LN_2 * angle.sin() + c * angle.cos())
+}
+
+// A ball dropped from `0.0` onto the floor at `1.0`, where each bounce takes
+// `restitution` times as long as the last. Returns the duration of the initial
+// fall, along with the index and midpoint of the bounce `t` falls in (where
+// the fall is bounce 0, with its midpoint at `0.0`).
+fn bounce_at(t: f64, bounces: u32, restitution: f64) -> (f64, i32, f64) {
+    let total = 1.0
+        + 2.0
+            * (1..=bounces as i32)
+                .map(|bounce| restitution.powi(bounce))
+                .sum::<f64>();
+    let fall = 1.0 / total;
+    let mut end = fall;
+    for bounce in 1..bounces as i32 {
+        let half = fall * restitution.powi(bounce);
+        if t < end + 2.0 * half {
+            return (fall, bounce, end + half);
+        }
+        end += 2.0 * half;
+    }
+    // Anything past the earlier bounces is in the last one
+    (
+        fall,
+        bounces as i32,
+        end + fall * restitution.powi(bounces as i32),
+    )
+}
+
+fn out_bounce_with(t: f64, bounces: u32, restitution: f64) -> f64 {
+    let (fall, bounce, mid) = bounce_at(t, bounces, restitution);
+    if t < fall {
+        (t / fall).powi(2)
+    } else if t >= 1.0 {
+        1.0
+    } else {
+        // Every bounce falls with the same acceleration as the initial fall
+        1.0 - restitution.powi(2 * bounce) + ((t - mid) / fall).powi(2)
+    }
+}
+
+fn dt_out_bounce_with(t: f64, bounces: u32, restitution: f64) -> f64 {
+    let (fall, _, mid) = bounce_at(t, bounces, restitution);
+    if t < fall {
+        2.0 * t / fall.powi(2)
+    } else if t >= 1.0 {
+        0.0
+    } else {
+        2.0 * (t - mid) / fall.powi(2)
+    }
+}
+
+// https://www.w3.org/TR/css-easing-1/#step-easing-algo
+fn steps_with(t: f64, steps: u32, jump: JumpMode) -> f64 {
+    let steps = f64::from(steps);
+    let mut step = (t * steps).floor();
+    if matches!(jump, JumpMode::Start | JumpMode::Both) {
+        step += 1.0;
+    }
+    if t >= 0.0 && step < 0.0 {
+        step = 0.0;
+    }
+    let jumps = match jump {
+        JumpMode::Start | JumpMode::End => steps,
+        JumpMode::None => steps - 1.0,
+        JumpMode::Both => steps + 1.0,
+    };
+    if t <= 1.0 && step > jumps {
+        step = jumps;
+    }
+    step / jumps
+}
+
+fn in_exponential_with(t: f64, strength: f64) -> f64 {
+    (f64::powf(2.0, strength * t) - 1.0) / (f64::powf(2.0, strength) - 1.0)
+}
+
+fn dt_in_exponential_with(t: f64, strength: f64) -> f64 {
+    strength * std::f64::consts::LN_2 * f64::powf(2.0, strength * t)
+        / (f64::powf(2.0, strength) - 1.0)
+}
+
+fn out_spring_with(t: f64, damping_ratio: f64, settle: f64) -> f64 {
+    if t >= 1.0 {
+        1.0
+    } else {
+        1.0 - unit_motion(damping_ratio, t * settle).0
+    }
+}
+
+fn dt_out_spring_with(t: f64, damping_ratio: f64, settle: f64) -> f64 {
+    if t >= 1.0 {
+        0.0
+    } else {
+        -unit_motion(damping_ratio, t * settle).1 * settle
+    }
+}
+
 // Miscellaneous ==============================================================
 
 fn identity(t: f64) -> f64 {
@@ -341,3 +1662,219 @@ fn identity(t: f64) -> f64 {
 fn lazy(t: f64) -> f64 {
     (t < 0.5).then(|| 0.0).unwrap_or_else(|| 2.0 * (t - 0.5))
 }
+
+fn dt_identity(_t: f64) -> f64 {
+    1.0
+}
+
+fn dt_lazy(t: f64) -> f64 {
+    if t < 0.5 {
+        0.0
+    } else {
+        2.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Animation as _, Interval};
+    use std::time::Duration;
+
+    #[test]
+    fn test_preset_derivatives() {
+        let delta = 1e-7;
+        for preset in Preset::ALL {
+            for i in 0..37 {
+                let t = i as f64 / 37.0 + 0.0031;
+                let difference = (preset.ease(t + delta) - preset.ease(t - delta)) / (2.0 * delta);
+                let derivative = preset.derivative(t);
+                assert!(
+                    (difference - derivative).abs() < 1e-4 * derivative.abs().max(1.0),
+                    "{:?} at {}: {} != {}",
+                    preset,
+                    t,
+                    derivative,
+                    difference
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parametric() {
+        let families = [
+            EaseFamily::power(2.5),
+            EaseFamily::back(1.70158),
+            EaseFamily::elastic(1.5, 0.4),
+            EaseFamily::bounce(3, 0.5),
+            EaseFamily::exponential(-4.0),
+            EaseFamily::spring(0.5),
+            EaseFamily::spring(1.0),
+            EaseFamily::spring(2.0),
+        ];
+        let delta = 1e-7;
+        for family in families {
+            for mode in [EaseMode::In, EaseMode::Out, EaseMode::InOut] {
+                let ease = Ease::Parametric(family, mode);
+                assert!(ease.ease(0.0).abs() < 1e-9, "{:?}", ease);
+                assert!((ease.ease(1.0) - 1.0).abs() < 1e-9, "{:?}", ease);
+                for i in 0..37 {
+                    let t = i as f64 / 37.0 + 0.0031;
+                    let difference = (ease.ease(t + delta) - ease.ease(t - delta)) / (2.0 * delta);
+                    let derivative = ease.derivative(t).unwrap();
+                    assert!(
+                        (difference - derivative).abs() < 1e-4 * derivative.abs().max(1.0),
+                        "{:?} at {}: {} != {}",
+                        ease,
+                        t,
+                        difference,
+                        derivative
+                    );
+                }
+            }
+        }
+
+        let back = Ease::back(EaseMode::Out, 1.70158);
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            assert!((back.ease(t) - Ease::out_back().ease(t)).abs() < 1e-9);
+        }
+        // The evaluators don't rely on validation to avoid panicking
+        assert!((out_bounce_with(0.3, 0, 0.5) - 0.09).abs() < 1e-9);
+
+        let steps = |steps, jump, t| Ease::steps(steps, jump).ease(t);
+        assert_eq!(steps(4, JumpMode::End, 0.3), 0.25);
+        assert_eq!(steps(4, JumpMode::Start, 0.3), 0.5);
+        assert_eq!(steps(3, JumpMode::None, 0.5), 0.5);
+        assert_eq!(steps(3, JumpMode::Both, 0.0), 0.25);
+        assert_eq!(steps(4, JumpMode::End, 1.0), 1.0);
+    }
+
+    #[test]
+    fn test_custom() {
+        // A lookup table, like one exported from a design tool
+        let table = [0.0, 0.1, 0.5, 1.0];
+        let ease = Ease::custom(move |t| {
+            let scaled = t.clamp(0.0, 1.0) * (table.len() - 1) as f64;
+            let index = (scaled as usize).min(table.len() - 2);
+            let fract = scaled - index as f64;
+            table[index] + (table[index + 1] - table[index]) * fract
+        });
+        assert!((ease.ease(0.5) - 0.3).abs() < 1e-9);
+        assert_eq!(ease.derivative(0.5), None);
+
+        let interval =
+            Interval::from_values(Duration::from_secs(1), 0.0f64, 10.0, Some(ease.clone()));
+        assert!((interval.sample(Duration::from_millis(500)) - 3.0).abs() < 1e-9);
+        assert_eq!(format!("{:?}", ease), "Ease::Custom(...)");
+    }
+
+    #[test]
+    fn test_combinators() {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+        let eases = [
+            Ease::in_quad().reverse(),
+            Ease::in_cubic().mirror(),
+            Ease::in_quad().chain(Ease::out_bounce(), 0.5),
+            Ease::out_back().scale_overshoot(0.5),
+            Ease::in_sine().mix(Ease::out_elastic(), 0.25),
+            Ease::power(EaseMode::In, 2.0).reverse(),
+        ];
+        for i in 0..=20 {
+            let t = i as f64 / 20.0;
+            assert!(close(eases[0].ease(t), Ease::out_quad().ease(t)));
+            assert!(close(eases[1].ease(t), Ease::in_out_cubic().ease(t)));
+            assert!(close(eases[5].ease(t), Ease::out_quad().ease(t)));
+            let mix = 0.75 * Ease::in_sine().ease(t) + 0.25 * Ease::out_elastic().ease(t);
+            assert!(close(eases[4].ease(t), mix));
+        }
+        assert!(matches!(eases[5], Ease::Parametric(_, EaseMode::Out)));
+        assert!(close(eases[2].ease(0.25), 0.125));
+        assert!(close(eases[2].ease(0.5), 0.5));
+
+        // Overshoot is halved, but in-range values are untouched
+        let peak = (0..=100)
+            .map(|i| eases[3].ease(i as f64 / 100.0))
+            .fold(0.0, f64::max);
+        let original = (0..=100)
+            .map(|i| Ease::out_back().ease(i as f64 / 100.0))
+            .fold(0.0, f64::max);
+        assert!(close(peak - 1.0, (original - 1.0) * 0.5));
+
+        let delta = 1e-7;
+        for ease in &eases {
+            for i in 0..37 {
+                let t = i as f64 / 37.0 + 0.0031;
+                let difference = (ease.ease(t + delta) - ease.ease(t - delta)) / (2.0 * delta);
+                let derivative = ease.derivative(t).unwrap();
+                assert!(
+                    (difference - derivative).abs() < 1e-4 * derivative.abs().max(1.0),
+                    "{:?} at {}: {} != {}",
+                    ease,
+                    t,
+                    difference,
+                    derivative
+                );
+            }
+        }
+
+        let json = serde_json::to_string(&eases[2]).unwrap();
+        let ease: Ease = serde_json::from_str(&json).unwrap();
+        assert!(close(ease.ease(0.75), eases[2].ease(0.75)));
+        assert!(serde_json::from_str::<Ease>(
+            r#"{"chain": {"first": "in_quad", "second": "linear", "split": 1.5}}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_serde() {
+        for preset in Preset::ALL {
+            let json = serde_json::to_string(&Ease::from(preset)).unwrap();
+            let ease: Ease = serde_json::from_str(&json).unwrap();
+            assert!(matches!(ease, Ease::Preset(p) if p == preset), "{}", json);
+        }
+        assert_eq!(
+            serde_json::to_string(&Ease::out_bounce()).unwrap(),
+            r#""out_bounce""#
+        );
+
+        let ease: Ease = serde_json::from_str(r#"{"ox":0.5,"oy":0.0,"ix":0.5,"iy":1.0}"#).unwrap();
+        assert!(matches!(ease, Ease::Bezier(bezier) if bezier.ox == 0.5 && bezier.iy == 1.0));
+
+        let ease: Ease = serde_json::from_str(
+            r#"{"family": {"spring": {"damping_ratio": 0.5}}, "mode": "out"}"#,
+        )
+        .unwrap();
+        assert!(
+            matches!(ease, Ease::Parametric(family, EaseMode::Out) if family == EaseFamily::spring(0.5))
+        );
+        assert!(serde_json::from_str::<Ease>(
+            r#"{"family": {"power": {"exponent": -1.0}}, "mode": "in"}"#
+        )
+        .is_err());
+        // Derived parameters aren't serialized
+        assert_eq!(
+            serde_json::to_string(&EaseFamily::spring(0.5)).unwrap(),
+            r#"{"spring":{"damping_ratio":0.5}}"#
+        );
+
+        assert!(serde_json::to_string(&Ease::Function(|t| t)).is_err());
+        assert!(serde_json::to_string(&Ease::custom(|t| t)).is_err());
+        assert!(serde_json::from_str::<Ease>(r#""out_bonce""#).is_err());
+
+        // Validation errors make it through, rather than a generic mismatch
+        let error = |json: &str| serde_json::from_str::<Ease>(json).unwrap_err().to_string();
+        assert!(error(
+            r#"{"family": {"bounce": {"bounces": 0, "restitution": 0.5}}, "mode": "out"}"#
+        )
+        .contains("bounce ease must bounce at least once"));
+        assert!(error(r#"{"points": [[0.0, 0.0]]}"#).contains("piecewise"));
+        assert!(error(
+            r#"{"mirror": {"chain": {"first": "in_quad", "second": "linear", "split": 1.5}}}"#
+        )
+        .contains("split must be between 0 and 1"));
+        assert!(error(r#""out_bonce""#).contains("unknown variant `out_bonce`"));
+    }
+}
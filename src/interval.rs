@@ -2,18 +2,20 @@ use crate::{
     ease::Ease,
     lerp_components,
     spline::{
-        bezier::{cubic_bezier, fixed_bezier},
+        bezier::{cubic_bezier, dt_cubic_bezier, fixed_bezier},
         bezier_ease::BezierEase,
         bezier_path::BezierPath,
-        spline_ease, SplineMap,
+        dt_spline_ease, spline_ease, SplineMap,
     },
     Animatable, Animation, BoundedAnimation,
 };
 use core::fmt::Debug;
+use gee::en::Num as _;
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 // A half-interval
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Deserialize, Serialize)]
 pub struct Frame<V: Animatable> {
     pub offset: Duration,
     pub value: V,
@@ -25,7 +27,7 @@ impl<V: Animatable> Frame<V> {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Deserialize, Serialize)]
 pub struct Interval<V: Animatable> {
     pub start: Duration,
     pub end: Duration,
@@ -82,6 +84,13 @@ impl<V: Animatable> Interval<V> {
         Self::new(Duration::ZERO, duration, from, to, ease, None, None)
     }
 
+    fn spline_time(&self, eased_time: f64) -> f64 {
+        self.reticulated_spline
+            .as_ref()
+            .map(|m| spline_ease(m, eased_time))
+            .unwrap_or(eased_time)
+    }
+
     pub fn percent_elapsed(&self, elapsed: Duration) -> f64 {
         if self.duration().is_zero() {
             0.0
@@ -144,11 +153,7 @@ impl<V: Animatable> Animation<V> for Interval<V> {
             .unwrap_or(percent_elapsed);
 
         // Map eased distance to spline time using spline map (or not)
-        let spline_time = self
-            .reticulated_spline
-            .as_ref()
-            .map(|m| spline_ease(&m, eased_time))
-            .unwrap_or(eased_time);
+        let spline_time = self.spline_time(eased_time);
 
         // Look up value along spline (or lerp)
         let value = self
@@ -158,6 +163,39 @@ impl<V: Animatable> Animation<V> for Interval<V> {
             .unwrap_or_else(|| lerp_components(self.from, self.to, spline_time));
         value
     }
+
+    fn sample_derivative(&self, elapsed: Duration) -> Option<V> {
+        if elapsed < self.start || elapsed > self.end || self.start == self.end {
+            // The value is held before and after the interval
+            return Some(self.from.sub(self.from));
+        }
+
+        // Chain rule, all the way down: d(value)/d(spline time) *
+        // d(spline time)/d(eased time) * d(eased time)/d(percent elapsed) *
+        // d(percent elapsed)/d(elapsed)
+        let percent_elapsed = self.percent_elapsed(elapsed);
+        let (eased_time, ease_slope) = match &self.ease {
+            Some(ease) => (
+                ease.ease(percent_elapsed),
+                ease.derivative(percent_elapsed)?,
+            ),
+            None => (percent_elapsed, 1.0),
+        };
+        let spline_time = self.spline_time(eased_time);
+        let tangent = self
+            .path
+            .as_ref()
+            .map(|p| dt_cubic_bezier(&self.from, &p.b1, &p.b2, &self.to, spline_time))
+            .unwrap_or_else(|| self.to.sub(self.from));
+        let spline_slope = self
+            .reticulated_spline
+            .as_ref()
+            .map(|m| dt_spline_ease(m, self.from.sub(self.from).distance_to(tangent)))
+            .unwrap_or(1.0);
+
+        let scale = spline_slope * ease_slope / self.duration().as_secs_f64();
+        Some(tangent.map(|c| (c.to_f64() * scale).cast()))
+    }
 }
 
 impl<V: Animatable> BoundedAnimation<V> for Interval<V> {
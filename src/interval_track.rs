@@ -7,14 +7,147 @@ use crate::{
     Animatable, Animation, BoundedAnimation,
 };
 use core::fmt::Debug;
-use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use std::{convert::TryFrom, time::Duration};
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum TrackError {
+    #[error("Track has no intervals")]
+    Empty,
+    #[error("Interval {index} ends before it starts")]
+    Reversed { index: usize },
+    #[error("Interval {index} starts before the interval preceding it")]
+    Unsorted { index: usize },
+    #[error("Interval {index} overlaps the interval preceding it")]
+    Overlapping { index: usize },
+    #[error("Interval {index} starts {gap:?} after the interval preceding it ends")]
+    Gap { index: usize, gap: Duration },
+    #[error("Interval {index} has zero length but changes value, which is only allowed when it's the only interval")]
+    ZeroLength { index: usize },
+    #[error("Interval {index} has a NaN or infinite value")]
+    IntervalNonFinite { index: usize },
+    #[error("Value {index} is NaN or infinite")]
+    ValueNonFinite { index: usize },
+    #[error(
+        "{style:?} bookending requires at least {required} values, but only {actual} were given"
+    )]
+    TooFewValues {
+        style: BookendStyle,
+        required: usize,
+        actual: usize,
+    },
+}
+
+// The distance from a value to itself is NaN if any of its components are NaN
+// or infinite
+fn is_non_finite<V: Animatable>(value: V) -> bool {
+    value.distance_to(value).is_nan()
+}
+
+fn validate_values<V: Animatable>(values: &[V]) -> Result<(), TrackError> {
+    match values.iter().position(|value| is_non_finite(*value)) {
+        Some(index) => Err(TrackError::ValueNonFinite { index }),
+        None => Ok(()),
+    }
+}
+
+// The ordering that lookups rely on, which every track is built with: no
+// interval ends before it starts, or starts before the one preceding it ends
+fn check_order<V: Animatable>(
+    prev: Option<&Interval<V>>,
+    interval: &Interval<V>,
+    index: usize,
+) -> Result<(), TrackError> {
+    if interval.start > interval.end {
+        return Err(TrackError::Reversed { index });
+    }
+    if let Some(prev) = prev {
+        if interval.start < prev.start {
+            return Err(TrackError::Unsorted { index });
+        }
+        if interval.start < prev.end {
+            return Err(TrackError::Overlapping { index });
+        }
+    }
+    Ok(())
+}
+
+fn validate_intervals<V: Animatable>(intervals: &[Interval<V>]) -> Result<(), TrackError> {
+    if intervals.is_empty() {
+        return Err(TrackError::Empty);
+    }
+    for (index, interval) in intervals.iter().enumerate() {
+        let prev = index.checked_sub(1).map(|prev| &intervals[prev]);
+        check_order(prev, interval, index)?;
+        // Zero-length intervals that hold a value are harmless (and are what
+        // `from_values` produces for repeated values), but a zero-length jump
+        // is never actually sampled
+        if interval.start == interval.end
+            && interval.from.distance_to(interval.to) > 0.0
+            && intervals.len() > 1
+        {
+            return Err(TrackError::ZeroLength { index });
+        }
+        let path_non_finite = matches!(
+            &interval.path,
+            Some(path) if is_non_finite(path.b1) || is_non_finite(path.b2)
+        );
+        if is_non_finite(interval.from) || is_non_finite(interval.to) || path_non_finite {
+            return Err(TrackError::IntervalNonFinite { index });
+        }
+        if let Some(prev) = prev {
+            if interval.start > prev.end {
+                return Err(TrackError::Gap {
+                    index,
+                    gap: interval.start - prev.end,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Remembers where the last sample of an [`IntervalTrack`] landed, so that
+/// sampling in order (as during playback) doesn't need to search the track.
+///
+/// A cursor can be used with any track, but it's only useful when reused for
+/// the same one.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IntervalCursor {
+    index: usize,
+}
 
-#[derive(Clone)]
+/// Intervals are always in order without overlapping, which is checked as
+/// they're added (or deserialized). Use [`IntervalTrack::validate`] to check
+/// for anything else, like gaps or infinite values, in tracks loaded from untrusted
+/// data.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(try_from = "IntervalTrackRepr<V>")]
 pub struct IntervalTrack<V: Animatable> {
     intervals: Vec<Interval<V>>,
     track_ease: Option<Ease>,
 }
 
+// `IntervalTrack` before its intervals have been checked for order
+#[derive(Deserialize)]
+struct IntervalTrackRepr<V: Animatable> {
+    intervals: Vec<Interval<V>>,
+    track_ease: Option<Ease>,
+}
+
+impl<V: Animatable> TryFrom<IntervalTrackRepr<V>> for IntervalTrack<V> {
+    type Error = TrackError;
+
+    fn try_from(repr: IntervalTrackRepr<V>) -> Result<Self, Self::Error> {
+        let mut track = Self::new().with_track_ease(repr.track_ease);
+        for interval in repr.intervals {
+            track.try_add_interval(interval)?;
+        }
+        Ok(track)
+    }
+}
+
 impl<V: Animatable> IntervalTrack<V> {
     pub fn new() -> Self {
         Self {
@@ -31,6 +164,32 @@ impl<V: Animatable> IntervalTrack<V> {
         Self::new().with_intervals(intervals)
     }
 
+    /// Like [`IntervalTrack::from_intervals`], but returns an error rather
+    /// than producing a track that can't be sampled correctly.
+    pub fn try_from_intervals(
+        intervals: impl IntoIterator<Item = Interval<V>>,
+    ) -> Result<Self, TrackError> {
+        let intervals = intervals.into_iter().collect::<Vec<_>>();
+        validate_intervals(&intervals)?;
+        Ok(Self {
+            intervals,
+            track_ease: None,
+        })
+    }
+
+    /// Like [`IntervalTrack::from_values`], but returns an error if any of
+    /// the values are NaN or infinite, or if the resulting track isn't valid.
+    pub fn try_from_values(
+        duration: Duration,
+        values: Vec<V>,
+        track_ease: Option<Ease>,
+    ) -> Result<Self, TrackError> {
+        validate_values(&values)?;
+        let track = Self::from_values(duration, values, track_ease);
+        track.validate()?;
+        Ok(track)
+    }
+
     pub fn from_values(duration: Duration, values: Vec<V>, track_ease: Option<Ease>) -> Self {
         match values.len() {
             0 => IntervalTrack::new(),
@@ -67,6 +226,31 @@ impl<V: Animatable> IntervalTrack<V> {
         }
     }
 
+    /// Like [`IntervalTrack::path`], but returns an error if any of the values
+    /// are NaN or infinite, if there aren't enough values for `bookend_style`,
+    /// or if the resulting track isn't valid.
+    pub fn try_path(
+        duration: Duration,
+        values: Vec<V>,
+        bookend_style: BookendStyle,
+        track_ease: Option<Ease>,
+        rectify: bool,
+    ) -> Result<Self, TrackError> {
+        validate_values(&values)?;
+        // Bookending only happens with 3 or more values
+        let required = bookend_style.required_values();
+        if values.len() >= 3 && values.len() < required {
+            return Err(TrackError::TooFewValues {
+                style: bookend_style,
+                required,
+                actual: values.len(),
+            });
+        }
+        let track = Self::path(duration, values, bookend_style, track_ease, rectify);
+        track.validate()?;
+        Ok(track)
+    }
+
     pub fn path(
         duration: Duration,
         values: Vec<V>,
@@ -156,9 +340,22 @@ impl<V: Animatable> IntervalTrack<V> {
         self
     }
 
+    /// Appends an interval.
+    ///
+    /// # Panics
+    /// If `interval` ends before it starts, or starts before the last interval
+    /// ends.
     pub fn add_interval(&mut self, interval: Interval<V>) -> &mut Self {
+        self.try_add_interval(interval)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Like [`IntervalTrack::add_interval`], but returns an error rather than
+    /// panicking.
+    pub fn try_add_interval(&mut self, interval: Interval<V>) -> Result<&mut Self, TrackError> {
+        check_order(self.intervals.last(), &interval, self.intervals.len())?;
         self.intervals.push(interval);
-        self
+        Ok(self)
     }
 
     pub fn add_intervals(&mut self, intervals: impl IntoIterator<Item = Interval<V>>) -> &mut Self {
@@ -169,10 +366,99 @@ impl<V: Animatable> IntervalTrack<V> {
     }
 
     pub fn current_interval(&self, elapsed: &Duration) -> Option<&Interval<V>> {
+        self.intervals.get(self.current_index(*elapsed))
+    }
+
+    // The first interval that ends after `elapsed`, or the last interval if
+    // they've all ended
+    fn current_index(&self, elapsed: Duration) -> usize {
         self.intervals
-            .iter()
-            .find(|interval| interval.end > *elapsed)
-            .or_else(|| self.intervals.last())
+            .partition_point(|interval| interval.end <= elapsed)
+            .min(self.intervals.len().saturating_sub(1))
+    }
+
+    fn is_current_index(&self, index: usize, elapsed: Duration) -> bool {
+        index < self.intervals.len()
+            && (index == 0 || self.intervals[index - 1].end <= elapsed)
+            && (index == self.intervals.len() - 1 || self.intervals[index].end > elapsed)
+    }
+
+    /// Like [`IntervalTrack::current_interval`], but starts looking from where
+    /// `cursor` last left off.
+    ///
+    /// This is constant time when sampling at or slightly after the previous
+    /// sample, and falls back to a binary search otherwise.
+    pub fn current_interval_with_cursor(
+        &self,
+        elapsed: Duration,
+        cursor: &mut IntervalCursor,
+    ) -> Option<&Interval<V>> {
+        if !self.is_current_index(cursor.index, elapsed) {
+            cursor.index = if self.is_current_index(cursor.index + 1, elapsed) {
+                cursor.index + 1
+            } else {
+                self.current_index(elapsed)
+            };
+        }
+        self.intervals.get(cursor.index)
+    }
+
+    /// Like [`Animation::sample`], but uses `cursor` to find the current
+    /// interval. See [`IntervalTrack::current_interval_with_cursor`].
+    pub fn sample_with_cursor(&self, elapsed: Duration, cursor: &mut IntervalCursor) -> V {
+        let eased_elapsed = self.eased_elapsed(elapsed);
+        self.current_interval_with_cursor(eased_elapsed, cursor)
+            .expect("tried to sample empty `IntervalTrack`")
+            .sample(eased_elapsed)
+    }
+
+    // The track ease, along with how far through the track `elapsed` is, if
+    // there's any easing to do
+    fn track_ease_percent(&self, elapsed: Duration) -> Option<(&Ease, f64)> {
+        match (&self.track_ease, self.intervals.first()) {
+            (Some(ease), Some(first)) if !self.duration().is_zero() => Some((
+                ease,
+                elapsed.saturating_sub(first.start).as_secs_f64() / self.duration().as_secs_f64(),
+            )),
+            _ => None,
+        }
+    }
+
+    fn eased_elapsed(&self, elapsed: Duration) -> Duration {
+        match self.track_ease_percent(elapsed) {
+            Some((ease, percent_elapsed)) => {
+                // The track holds its values beyond either end anyway, so
+                // overshooting eases can be clamped rather than producing a
+                // negative duration
+                let eased = ease.ease(percent_elapsed);
+                let eased = if eased.is_nan() {
+                    0.0
+                } else {
+                    eased.clamp(0.0, 1.0)
+                };
+                self.duration().mul_f64(eased)
+            }
+            None => elapsed,
+        }
+    }
+
+    /// Checks that this track can be sampled correctly.
+    pub fn validate(&self) -> Result<(), TrackError> {
+        validate_intervals(&self.intervals)
+    }
+
+    /// Like [`Animation::sample`], but returns an error rather than panicking
+    /// if the track is empty.
+    ///
+    /// The ordering that sampling relies on is checked once as the track is
+    /// built, so this is as fast as `sample`. Use [`IntervalTrack::validate`]
+    /// to check loaded tracks for problems that don't panic, like gaps.
+    pub fn try_sample(&self, elapsed: Duration) -> Result<V, TrackError> {
+        if self.intervals.is_empty() {
+            Err(TrackError::Empty)
+        } else {
+            Ok(self.sample(elapsed))
+        }
     }
 
     pub fn length(&self) -> f64 {
@@ -183,7 +469,11 @@ impl<V: Animatable> IntervalTrack<V> {
 
     // Returns the sampled value at elapsed, as well as the values for any elapsed keyframes
     pub fn keyframe_sample(&self, elapsed: Duration) -> Vec<V> {
-        std::iter::once(self.intervals[0].from)
+        let (first, current) = match (self.intervals.first(), self.current_interval(&elapsed)) {
+            (Some(first), Some(current)) => (first, current),
+            _ => return vec![],
+        };
+        std::iter::once(first.from)
             .chain(
                 self.intervals
                     .iter()
@@ -191,35 +481,31 @@ impl<V: Animatable> IntervalTrack<V> {
                         elapsed > interval.start
                             && !interval.changes_after(elapsed - interval.start)
                     })
-                    .into_iter()
                     .map(|interval| interval.to),
             )
-            .chain(std::iter::once(
-                self.current_interval(&elapsed)
-                    .map(|interval| interval.sample(elapsed))
-                    .expect("Animation has no current interval!"),
-            ))
+            .chain(std::iter::once(current.sample(elapsed)))
             .collect()
     }
 }
 
 impl<V: Animatable> Animation<V> for IntervalTrack<V> {
     fn sample(&self, elapsed: Duration) -> V {
-        let eased_elapsed = self
-            .track_ease
-            .as_ref()
-            .map(|ease| {
-                self.duration().mul_f64(ease.ease(
-                    (elapsed - self.intervals[0].start).as_secs_f64()
-                        / self.duration().as_secs_f64(),
-                ))
-            })
-            .unwrap_or(elapsed);
-
+        let eased_elapsed = self.eased_elapsed(elapsed);
         self.current_interval(&eased_elapsed)
             .expect("tried to sample empty `IntervalTrack`")
             .sample(eased_elapsed)
     }
+
+    fn sample_derivative(&self, elapsed: Duration) -> Option<V> {
+        let ease_slope = match self.track_ease_percent(elapsed) {
+            Some((ease, percent_elapsed)) => ease.derivative(percent_elapsed)?,
+            None => 1.0,
+        };
+        let eased_elapsed = self.eased_elapsed(elapsed);
+        self.current_interval(&eased_elapsed)?
+            .sample_derivative(eased_elapsed)
+            .map(|derivative| derivative.map(|c| (c.to_f64() * ease_slope).cast()))
+    }
 }
 
 impl<V: Animatable> BoundedAnimation<V> for IntervalTrack<V> {
@@ -232,6 +518,7 @@ impl<V: Animatable> BoundedAnimation<V> for IntervalTrack<V> {
 }
 
 /// Different ways of selecting additional control points at either end of a series of values.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum BookendStyle {
     /// Repeat the first and last values
     Repeat,
@@ -245,6 +532,17 @@ pub enum BookendStyle {
     None,
 }
 
+impl BookendStyle {
+    fn required_values(self) -> usize {
+        match self {
+            BookendStyle::Linear => 2,
+            BookendStyle::Loop | BookendStyle::Spiral => 3,
+            BookendStyle::None => 4,
+            BookendStyle::Repeat => 0,
+        }
+    }
+}
+
 fn bookend<V: Animatable>(values: Vec<V>, style: BookendStyle) -> Vec<V> {
     if values.is_empty() {
         values
@@ -350,10 +648,19 @@ fn accumulate_lengths(lengths: &Vec<f64>) -> Vec<f64> {
 }
 
 fn constant_velocity_durations(distances: &Vec<f64>, duration: Duration) -> Vec<Duration> {
-    distances
-        .iter()
-        .map(|distance| duration.mul_f64(distance / distances.last().unwrap()))
-        .collect()
+    let total = *distances.last().unwrap();
+    if total == 0.0 {
+        // The values never move, so just space them out evenly
+        let count = (distances.len() - 1).max(1) as f64;
+        (0..distances.len())
+            .map(|index| duration.mul_f64(index as f64 / count))
+            .collect()
+    } else {
+        distances
+            .iter()
+            .map(|distance| duration.mul_f64(distance / total))
+            .collect()
+    }
 }
 
 impl<V: Animatable> Debug for IntervalTrack<V> {
@@ -367,3 +674,215 @@ impl<V: Animatable> Debug for IntervalTrack<V> {
         write!(f, "\n\ttrack_ease:\t{:?}", self.track_ease)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn linear(start: f64, end: f64, from: f64, to: f64) -> Interval<f64> {
+        Interval::linear(
+            Frame::new(Duration::from_secs_f64(start), from),
+            Frame::new(Duration::from_secs_f64(end), to),
+        )
+    }
+
+    #[test]
+    fn test_validation() {
+        let empty = IntervalTrack::<f64>::new();
+        assert_eq!(empty.validate(), Err(TrackError::Empty));
+        assert_eq!(
+            empty.try_sample(Duration::from_secs_f64(0.5)),
+            Err(TrackError::Empty)
+        );
+        assert!(empty
+            .keyframe_sample(Duration::from_secs_f64(0.5))
+            .is_empty());
+
+        let track = IntervalTrack::try_from_intervals([
+            linear(0.0, 1.0, 0.0, 1.0),
+            linear(1.0, 2.0, 1.0, 0.0),
+        ])
+        .unwrap();
+        assert_eq!(track.try_sample(Duration::from_secs_f64(1.5)), Ok(0.5));
+        // Overshooting track eases hold the ends rather than panicking
+        let track = track.with_track_ease(Some(Ease::in_out_back()));
+        assert_eq!(track.try_sample(Duration::from_secs_f64(0.1)), Ok(0.0));
+
+        let invalid = |intervals: Vec<Interval<f64>>| {
+            IntervalTrack::try_from_intervals(intervals).unwrap_err()
+        };
+        assert_eq!(
+            invalid(vec![linear(1.0, 0.0, 0.0, 1.0)]),
+            TrackError::Reversed { index: 0 }
+        );
+        assert_eq!(
+            invalid(vec![linear(1.0, 2.0, 0.0, 1.0), linear(0.0, 1.0, 0.0, 1.0)]),
+            TrackError::Unsorted { index: 1 }
+        );
+        assert_eq!(
+            invalid(vec![linear(0.0, 2.0, 0.0, 1.0), linear(1.0, 3.0, 0.0, 1.0)]),
+            TrackError::Overlapping { index: 1 }
+        );
+        assert_eq!(
+            invalid(vec![linear(0.0, 1.0, 0.0, 1.0), linear(2.0, 3.0, 0.0, 1.0)]),
+            TrackError::Gap {
+                index: 1,
+                gap: Duration::from_secs_f64(1.0)
+            }
+        );
+        assert_eq!(
+            invalid(vec![linear(0.0, 1.0, 0.0, 1.0), linear(1.0, 1.0, 1.0, 2.0)]),
+            TrackError::ZeroLength { index: 1 }
+        );
+        let mut track = IntervalTrack::from_interval(linear(0.0, 2.0, 0.0, 1.0));
+        assert_eq!(
+            track.try_add_interval(linear(1.0, 3.0, 0.0, 1.0)).err(),
+            Some(TrackError::Overlapping { index: 1 })
+        );
+        assert_eq!(track.validate(), Ok(()));
+        assert_eq!(
+            invalid(vec![linear(0.0, 1.0, 0.0, f64::NAN)]),
+            TrackError::IntervalNonFinite { index: 0 }
+        );
+
+        // Repeated values produce zero-length intervals that hold a value
+        let repeated = IntervalTrack::try_from_values(
+            Duration::from_secs_f64(1.0),
+            vec![0.0, 1.0, 1.0, 2.0],
+            None,
+        )
+        .unwrap();
+        assert_eq!(repeated.validate(), Ok(()));
+        assert_eq!(repeated.try_sample(Duration::from_secs_f64(0.5)), Ok(1.0));
+        assert!(IntervalTrack::try_path(
+            Duration::from_secs_f64(1.0),
+            vec![0.0, 1.0, 1.0, 2.0],
+            BookendStyle::Repeat,
+            None,
+            false
+        )
+        .is_ok());
+
+        assert_eq!(
+            IntervalTrack::try_path(
+                Duration::from_secs_f64(1.0),
+                vec![0.0, 1.0, 2.0],
+                BookendStyle::None,
+                None,
+                false
+            )
+            .unwrap_err(),
+            TrackError::TooFewValues {
+                style: BookendStyle::None,
+                required: 4,
+                actual: 3
+            }
+        );
+        assert_eq!(
+            IntervalTrack::try_from_values(Duration::from_secs_f64(1.0), vec![0.0, f64::NAN], None)
+                .unwrap_err(),
+            TrackError::ValueNonFinite { index: 1 }
+        );
+        assert_eq!(
+            IntervalTrack::try_from_values(
+                Duration::from_secs_f64(1.0),
+                vec![f64::INFINITY, 0.0],
+                None
+            )
+            .unwrap_err(),
+            TrackError::ValueNonFinite { index: 0 }
+        );
+    }
+
+    #[test]
+    fn test_derivative_edge_cases() {
+        assert!(IntervalTrack::<f64>::new()
+            .sample_derivative(Duration::from_secs_f64(1.0))
+            .is_none());
+
+        let eased = |intervals: Vec<Interval<f64>>| {
+            IntervalTrack::from_intervals(intervals).with_track_ease(Some(Ease::in_out_sine()))
+        };
+        // Before the first interval
+        let track = eased(vec![linear(1.0, 2.0, 0.0, 1.0)]);
+        assert_eq!(
+            track.sample_derivative(Duration::from_secs_f64(0.5)),
+            Some(0.0)
+        );
+        // A zero-duration track
+        let track = eased(vec![linear(0.0, 0.0, 1.0, 1.0)]);
+        assert_eq!(
+            track.sample_derivative(Duration::from_secs_f64(0.0)),
+            Some(0.0)
+        );
+
+        // Velocity and acceleration are built on the analytic derivative
+        let track =
+            IntervalTrack::from_intervals([linear(0.0, 1.0, 0.0, 1.0), linear(1.0, 2.0, 1.0, 3.0)]);
+        assert_eq!(
+            track.sample_velocity(Duration::from_secs_f64(1.5), 0.1),
+            2.0
+        );
+        assert_eq!(
+            track.velocity(3, Duration::from_secs_f64(1.5)),
+            [1.0, 1.0, 2.0, 2.0]
+        );
+        assert_eq!(
+            track.acceleration(2, Duration::from_secs_f64(1.5)),
+            [0.0, 1.0, 0.0]
+        );
+
+        // The derivative at the end comes from inside the track, so reversing
+        // it starts out moving
+        assert_eq!(
+            track.sample_derivative(Duration::from_secs_f64(2.0)),
+            Some(2.0)
+        );
+        assert_eq!(
+            track.sample_derivative(Duration::from_secs_f64(2.5)),
+            Some(0.0)
+        );
+        assert_eq!(
+            track.rev().sample_derivative(Duration::from_secs_f64(0.0)),
+            Some(-2.0)
+        );
+    }
+
+    #[test]
+    fn test_serde() {
+        let track = IntervalTrack::path(
+            Duration::from_secs_f64(1.0),
+            vec![(0.0f64, 0.0f64), (1.0, 2.0), (3.0, 1.0)],
+            BookendStyle::Spiral,
+            Some(Ease::in_out_cubic()),
+            true,
+        );
+        let json = serde_json::to_string(&track).unwrap();
+        let loaded: IntervalTrack<(f64, f64)> = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.validate(), Ok(()));
+        for i in 0..=10 {
+            let elapsed = Duration::from_secs_f64(i as f64 / 10.0);
+            let (a, b) = (loaded.sample(elapsed), track.sample(elapsed));
+            assert!(a.distance_to(b) < 1e-9, "{:?} != {:?}", a, b);
+        }
+
+        // Deserialization checks order just like building a track does
+        let intervals = [linear(0.0, 1.0, 0.0, 1.0), linear(1.0, 0.5, 1.0, 0.0)];
+        let json = format!(
+            r#"{{"intervals": {}, "track_ease": null}}"#,
+            serde_json::to_string(&intervals).unwrap()
+        );
+        let error = serde_json::from_str::<IntervalTrack<f64>>(&json)
+            .err()
+            .unwrap();
+        assert!(error
+            .to_string()
+            .contains("Interval 1 ends before it starts"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Interval 1 starts before the interval preceding it")]
+    fn test_add_unsorted() {
+        IntervalTrack::from_intervals([linear(1.0, 2.0, 0.0, 1.0), linear(0.0, 1.0, 0.0, 1.0)]);
+    }
+}
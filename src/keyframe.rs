@@ -0,0 +1,326 @@
+use crate::{
+    interval::Interval,
+    interval_track::IntervalTrack,
+    spline::{bezier_ease::BezierEase, bezier_path::BezierPath, SplineMap},
+    Animatable, Animation, BoundedAnimation,
+};
+use std::time::Duration;
+
+// The handles of `BezierEase::linear`, used when only one side of an interval
+// specifies an ease
+const LINEAR_EASE_OUT: (f64, f64) = (0.16, 0.16);
+const LINEAR_EASE_IN: (f64, f64) = (0.84, 0.84);
+
+/// A value at a specific point in time, along with how to get to and from
+/// the keyframes on either side of it.
+///
+/// Temporal eases are given as control points of a [`BezierEase`], so
+/// `ease_out` is the `(ox, oy)` of the interval leaving this keyframe, and
+/// `ease_in` is the `(ix, iy)` of the interval arriving at it. Spatial tangents
+/// are relative to `value`, like in After Effects.
+#[derive(Clone, Copy, Debug)]
+pub struct Keyframe<V: Animatable> {
+    pub time: Duration,
+    pub value: V,
+    pub ease_in: Option<(f64, f64)>,
+    pub ease_out: Option<(f64, f64)>,
+    pub tangent_in: Option<V>,
+    pub tangent_out: Option<V>,
+    /// Holds `value` until the next keyframe, rather than interpolating.
+    pub hold: bool,
+}
+
+impl<V: Animatable> Keyframe<V> {
+    pub fn new(time: Duration, value: V) -> Self {
+        Self {
+            time,
+            value,
+            ease_in: None,
+            ease_out: None,
+            tangent_in: None,
+            tangent_out: None,
+            hold: false,
+        }
+    }
+
+    pub fn with_ease_in(mut self, ix: f64, iy: f64) -> Self {
+        self.ease_in = Some((ix, iy));
+        self
+    }
+
+    pub fn with_ease_out(mut self, ox: f64, oy: f64) -> Self {
+        self.ease_out = Some((ox, oy));
+        self
+    }
+
+    pub fn with_tangents(mut self, tangent_in: V, tangent_out: V) -> Self {
+        self.tangent_in = Some(tangent_in);
+        self.tangent_out = Some(tangent_out);
+        self
+    }
+
+    pub fn with_hold(mut self, hold: bool) -> Self {
+        self.hold = hold;
+        self
+    }
+
+    // The interval between this keyframe and the next one
+    fn interval_to(&self, next: &Self) -> Interval<V> {
+        if self.hold {
+            return Interval::new(
+                self.time, next.time, self.value, self.value, None, None, None,
+            );
+        }
+
+        let ease = if self.ease_out.is_some() || next.ease_in.is_some() {
+            let (ox, oy) = self.ease_out.unwrap_or(LINEAR_EASE_OUT);
+            let (ix, iy) = next.ease_in.unwrap_or(LINEAR_EASE_IN);
+            Some(BezierEase::new_ease(ox, oy, ix, iy))
+        } else {
+            None
+        };
+
+        let (path, reticulated_spline) = if self.tangent_out.is_some() || next.tangent_in.is_some()
+        {
+            let b1 = self
+                .tangent_out
+                .map_or(self.value, |tangent| self.value.add(tangent));
+            let b2 = next
+                .tangent_in
+                .map_or(next.value, |tangent| next.value.add(tangent));
+            (
+                Some(BezierPath::new(b1, b2)),
+                Some(SplineMap::from_bezier(
+                    &self.value,
+                    &b1,
+                    &b2,
+                    &next.value,
+                    true,
+                )),
+            )
+        } else {
+            (None, None)
+        };
+
+        Interval::new(
+            self.time,
+            next.time,
+            self.value,
+            next.value,
+            ease,
+            path,
+            reticulated_spline,
+        )
+    }
+}
+
+/// An editable list of [`Keyframe`]s, which is kept compiled into an
+/// [`IntervalTrack`] for playback.
+///
+/// Keyframes are kept sorted by time, and no two keyframes can share the same
+/// time; adding a keyframe at the time of an existing one replaces it.
+/// Operations that can reorder keyframes return the new index of the keyframe
+/// they affected.
+#[derive(Debug)]
+pub struct Track<V: Animatable> {
+    keyframes: Vec<Keyframe<V>>,
+    intervals: IntervalTrack<V>,
+}
+
+impl<V: Animatable> Default for Track<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Animatable> Track<V> {
+    pub fn new() -> Self {
+        Self {
+            keyframes: vec![],
+            intervals: IntervalTrack::new(),
+        }
+    }
+
+    pub fn from_keyframes(keyframes: impl IntoIterator<Item = Keyframe<V>>) -> Self {
+        let mut track = Self::new();
+        for keyframe in keyframes {
+            track.place(keyframe);
+        }
+        track.compile();
+        track
+    }
+
+    pub fn with_keyframe(mut self, keyframe: Keyframe<V>) -> Self {
+        self.insert(keyframe);
+        self
+    }
+
+    pub fn keyframes(&self) -> &[Keyframe<V>] {
+        &self.keyframes
+    }
+
+    pub fn keyframe(&self, index: usize) -> Option<&Keyframe<V>> {
+        self.keyframes.get(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.keyframes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keyframes.is_empty()
+    }
+
+    /// The compiled representation of this track.
+    pub fn intervals(&self) -> &IntervalTrack<V> {
+        &self.intervals
+    }
+
+    /// Adds a keyframe, replacing any keyframe at the same time.
+    pub fn insert(&mut self, keyframe: Keyframe<V>) -> usize {
+        let index = self.place(keyframe);
+        self.compile();
+        index
+    }
+
+    /// Removes the keyframe at `index`.
+    ///
+    /// # Panics
+    /// If `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Keyframe<V> {
+        let keyframe = self.keyframes.remove(index);
+        self.compile();
+        keyframe
+    }
+
+    /// Moves the keyframe at `index` to a new time, replacing any keyframe
+    /// already at that time.
+    ///
+    /// # Panics
+    /// If `index` is out of bounds.
+    pub fn move_keyframe(&mut self, index: usize, time: Duration) -> usize {
+        self.update(index, |keyframe| keyframe.time = time)
+    }
+
+    /// Changes the value of the keyframe at `index`.
+    ///
+    /// # Panics
+    /// If `index` is out of bounds.
+    pub fn set_value(&mut self, index: usize, value: V) {
+        self.update(index, |keyframe| keyframe.value = value);
+    }
+
+    /// Edits the keyframe at `index` in place.
+    ///
+    /// # Panics
+    /// If `index` is out of bounds.
+    pub fn update(&mut self, index: usize, f: impl FnOnce(&mut Keyframe<V>)) -> usize {
+        let mut keyframe = self.keyframes.remove(index);
+        f(&mut keyframe);
+        self.insert(keyframe)
+    }
+
+    // Inserts a keyframe in sorted order without recompiling
+    fn place(&mut self, keyframe: Keyframe<V>) -> usize {
+        match self
+            .keyframes
+            .binary_search_by_key(&keyframe.time, |keyframe| keyframe.time)
+        {
+            Ok(index) => {
+                self.keyframes[index] = keyframe;
+                index
+            }
+            Err(index) => {
+                self.keyframes.insert(index, keyframe);
+                index
+            }
+        }
+    }
+
+    fn compile(&mut self) {
+        self.intervals = match self.keyframes.as_slice() {
+            [] => IntervalTrack::new(),
+            [keyframe] => IntervalTrack::from_interval(Interval::new(
+                keyframe.time,
+                keyframe.time,
+                keyframe.value,
+                keyframe.value,
+                None,
+                None,
+                None,
+            )),
+            keyframes => IntervalTrack::from_intervals(
+                keyframes
+                    .windows(2)
+                    .map(|window| window[0].interval_to(&window[1])),
+            ),
+        };
+    }
+}
+
+impl<V: Animatable> Animation<V> for Track<V> {
+    fn sample(&self, elapsed: Duration) -> V {
+        self.intervals.sample(elapsed)
+    }
+
+    fn sample_derivative(&self, elapsed: Duration) -> Option<V> {
+        self.intervals.sample_derivative(elapsed)
+    }
+}
+
+impl<V: Animatable> BoundedAnimation<V> for Track<V> {
+    fn duration(&self) -> Duration {
+        self.intervals.duration()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editing() {
+        let mut track = Track::from_keyframes([
+            Keyframe::new(Duration::from_secs_f64(2.0), 20.0),
+            Keyframe::new(Duration::from_secs_f64(0.0), 0.0),
+        ]);
+        assert_eq!(track.duration(), Duration::from_secs_f64(2.0));
+        assert_eq!(track.sample(Duration::from_secs_f64(1.0)), 10.0);
+
+        // Replaces the keyframe at the same time
+        assert_eq!(
+            track.insert(Keyframe::new(Duration::from_secs_f64(2.0), 40.0)),
+            1
+        );
+        assert_eq!(track.len(), 2);
+        assert_eq!(track.sample(Duration::from_secs_f64(1.0)), 20.0);
+
+        let index = track.insert(Keyframe::new(Duration::from_secs_f64(1.0), 0.0).with_hold(true));
+        assert_eq!(index, 1);
+        assert_eq!(track.sample(Duration::from_secs_f64(1.5)), 0.0);
+
+        assert_eq!(track.move_keyframe(1, Duration::from_secs_f64(3.0)), 2);
+        assert_eq!(track.duration(), Duration::from_secs_f64(3.0));
+        assert_eq!(track.sample(Duration::from_secs_f64(1.0)), 20.0);
+        assert_eq!(track.sample(Duration::from_secs_f64(2.5)), 20.0);
+
+        track.remove(2);
+        track.set_value(0, 20.0);
+        assert_eq!(track.sample(Duration::from_secs_f64(1.0)), 30.0);
+    }
+
+    #[test]
+    fn test_eases_and_tangents() {
+        let track = Track::from_keyframes([
+            Keyframe::new(Duration::from_secs_f64(0.0), (0.0f64, 0.0))
+                .with_ease_out(0.5, 0.0)
+                .with_tangents((0.0, 0.0), (0.0, 10.0)),
+            Keyframe::new(Duration::from_secs_f64(1.0), (10.0, 0.0))
+                .with_ease_in(0.5, 1.0)
+                .with_tangents((0.0, 10.0), (0.0, 0.0)),
+        ]);
+        let (x, y) = track.sample(Duration::from_secs_f64(0.5));
+        assert!((x - 5.0).abs() < 1e-3 && y > 0.0, "{:?}", (x, y));
+        assert_eq!(track.sample(Duration::from_secs_f64(1.0)), (10.0, 0.0));
+    }
+}
@@ -4,23 +4,33 @@ pub mod after_effects;
 mod combinators;
 mod component_wise;
 pub mod constant;
+pub mod css;
+pub mod decay;
+pub mod description;
 pub mod ease;
 pub mod function;
 pub mod interval;
 pub mod interval_track;
+pub mod keyframe;
 mod lerp;
+pub mod player;
 pub mod spline;
+pub mod spring;
+pub mod state_machine;
 pub mod structured;
+pub mod timeline;
 
 pub use self::{
-    combinators::*, component_wise::*, constant::*, ease::*, interval::*, interval_track::*,
-    lerp::*, spline::*, structured::*,
+    combinators::*, component_wise::*, constant::*, css::*, decay::*, description::*, ease::*,
+    interval::*, interval_track::*, keyframe::*, lerp::*, player::*, spline::*, spring::*,
+    state_machine::*, structured::*, timeline::*,
 };
 
 use gee::en::Num as _;
 pub use paste;
 use std::{
     fmt::Debug,
+    rc::Rc,
     time::{Duration, Instant},
 };
 
@@ -30,7 +40,8 @@ use std::{
 /// combined using all sorts of cool combinators, while still producing an
 /// `Animation` on the other end.
 ///
-/// Implementors should only implement [`Animation::sample`].
+/// Implementors should only implement [`Animation::sample`], and
+/// [`Animation::sample_derivative`] if the derivative is known analytically.
 pub trait Animation<V: Animatable>: Debug {
     /// Samples the animation at the specified duration.
     ///
@@ -48,6 +59,19 @@ pub trait Animation<V: Animatable>: Debug {
     /// - The result is unspecified if `elapsed` is negative.
     fn sample(&self, elapsed: Duration) -> V;
 
+    /// Samples the rate of change of the animation (in units per second) at
+    /// the specified duration.
+    ///
+    /// At the end of a [`BoundedAnimation`], this is the derivative approaching
+    /// the end from inside the animation; it's only zero afterwards.
+    ///
+    /// This is `None` unless the animation can calculate its derivative
+    /// analytically. [`Animation::sample_velocity`] falls back on a finite
+    /// difference in that case.
+    fn sample_derivative(&self, _elapsed: Duration) -> Option<V> {
+        None
+    }
+
     /// Allows you to use combinators on a mutable reference to an animation.
     ///
     /// This is typically only useful if you're using trait objects.
@@ -86,6 +110,61 @@ pub trait Animation<V: Animatable>: Debug {
         Cutoff::new(self, duration)
     }
 
+    /// Waits for the specified duration before starting this animation.
+    ///
+    /// The first value is held during the delay. If this animation is a
+    /// `BoundedAnimation`, then the resulting animation is a `BoundedAnimation`
+    /// that's longer by `delay`.
+    fn delay(self, delay: Duration) -> Delay<Self, V>
+    where
+        Self: Sized,
+    {
+        Delay::new(self, delay)
+    }
+
+    /// Starts this animation partway in, skipping the specified duration.
+    ///
+    /// If this animation is a `BoundedAnimation`, then the resulting animation
+    /// is a `BoundedAnimation` that's shorter by `offset`.
+    fn offset(self, offset: Duration) -> Offset<Self, V>
+    where
+        Self: Sized,
+    {
+        Offset::new(self, offset)
+    }
+
+    /// Plays this animation at a multiple of its normal speed.
+    ///
+    /// A `factor` of `2.0` plays twice as fast, and `0.5` plays in slow motion.
+    /// To play backwards, see [`BoundedAnimation::rev`].
+    ///
+    /// # Panics
+    /// If `factor` isn't positive and finite.
+    fn speed(self, factor: f64) -> Speed<Self, V>
+    where
+        Self: Sized,
+    {
+        Speed::new(self, factor)
+    }
+
+    /// Samples this animation at the time (in seconds) given by another
+    /// animation.
+    ///
+    /// This is equivalent to After Effects' time remapping: a `time` that
+    /// holds still freezes this animation, and one that decreases plays it
+    /// backwards. Negative and NaN times are clamped to zero, and times too
+    /// large for a `Duration` are clamped to `Duration::MAX`.
+    ///
+    /// If `time` is a `BoundedAnimation`, then the resulting animation is a
+    /// `BoundedAnimation` with the same duration as `time`.
+    fn remap<T>(self, time: T) -> Remap<Self, T, V>
+    where
+        Self: Sized,
+        T: Animation<f64>,
+    {
+        Remap::new(self, time)
+    }
+
     fn interrupt<A>(
         self,
         other: A,
@@ -99,14 +178,98 @@ pub trait Animation<V: Animatable>: Debug {
         Interrupt::new(self.cutoff(interrupt_t), other, interrupt_t, transition_t)
     }
 
+    /// Derives a new animated value by running `f` on every sample.
+    ///
+    /// If this animation is a `BoundedAnimation`, then the resulting animation
+    /// is a `BoundedAnimation` with the same duration.
+    ///
+    /// # Examples
+    /// ```
+    /// use celerity::{Animation as _, Interval};
+    /// use std::time::Duration;
+    ///
+    /// let x = Interval::from_values(Duration::from_secs(1), 0.0f32, 10.0, None);
+    /// let point = x.map(|x| gee::Point::new(x, x * 2.0));
+    /// ```
+    fn map<W, F>(self, f: F) -> Map<Self, F, V, W>
+    where
+        Self: Sized,
+        W: Animatable,
+        F: Fn(V) -> W,
+    {
+        Map::new(self, f)
+    }
+
+    /// Animates this and another animation in lockstep, producing a
+    /// [`Zipped`] pair of their values.
+    ///
+    /// If both animations are `BoundedAnimation`s, then the resulting
+    /// animation is a `BoundedAnimation` lasting as long as the longer of the
+    /// two. To zip more than two animations, see [`Zip::new`].
+    fn zip<B, W>(self, other: B) -> Zip<(Self, B), (V, W)>
+    where
+        Self: Sized,
+        B: Animation<W>,
+        W: Animatable,
+    {
+        Zip::new((self, other))
+    }
+
+    /// Mixes this animation with another, using a weight given by a third.
+    ///
+    /// A weight of `0.0` is entirely this animation, and `1.0` is entirely
+    /// `other`. The weight isn't clamped, so values outside of that range
+    /// extrapolate.
+    ///
+    /// If all three animations are `BoundedAnimation`s, then the resulting
+    /// animation is a `BoundedAnimation` lasting as long as the longest of
+    /// them.
+    fn blend<B, W>(self, other: B, weight: W) -> Blend<Self, B, W, V>
+    where
+        Self: Sized,
+        B: Animation<V>,
+        W: Animation<f64>,
+    {
+        Blend::new(self, other, weight)
+    }
+
+    /// Layers another animation on top of this one by adding their values
+    /// together component-wise.
+    ///
+    /// If `relative` is `true`, then `overlay` is treated as an offset from its
+    /// own first sample, so an overlay that starts away from zero won't cause
+    /// a jump. Additive animations can be layered on top of each other to
+    /// combine any number of overlays.
+    ///
+    /// If both animations are `BoundedAnimation`s, then the resulting
+    /// animation is a `BoundedAnimation` lasting as long as the longer of the
+    /// two.
+    fn additive<B>(self, overlay: B, relative: bool) -> Additive<Self, B, V>
+    where
+        Self: Sized,
+        B: Animation<V>,
+    {
+        Additive::new(self, overlay, relative)
+    }
+
     fn path(&self, sample_count: usize, sample_duration: Duration) -> Vec<V> {
         (0..sample_count + 1)
             .map(|i| self.sample(sample_duration.mul_f64(i.to_f64() / sample_count.to_f64())))
             .collect()
     }
 
-    // Sampling error can occur arround tight curves, showing reduced velocity
+    // Uses the analytic derivative when it's known. Otherwise, sampling error
+    // can occur arround tight curves, showing reduced velocity
     fn velocity(&self, sample_count: usize, sample_duration: Duration) -> Vec<V> {
+        let derivatives = (0..sample_count + 1)
+            .map(|i| {
+                self.sample_derivative(sample_duration.mul_f64(i.to_f64() / sample_count.to_f64()))
+            })
+            .collect::<Option<Vec<_>>>();
+        if let Some(derivatives) = derivatives {
+            return derivatives;
+        }
+
         let sample_delta = sample_duration.as_secs_f64() / sample_count.to_f64();
         self.path(sample_count + 1, sample_duration)
             .windows(2)
@@ -116,8 +279,11 @@ pub trait Animation<V: Animatable>: Debug {
             .collect()
     }
 
-    // Velocity in units/second
+    // Velocity in units/second, using the analytic derivative when it's known
     fn sample_velocity(&self, elapsed: Duration, delta: f64) -> V {
+        if let Some(derivative) = self.sample_derivative(elapsed) {
+            return derivative;
+        }
         let inverse_delta = 1.0 / delta;
         let a = self.sample(elapsed - Duration::from_secs_f64(delta));
         let b = self.sample(elapsed + Duration::from_secs_f64(delta));
@@ -125,7 +291,8 @@ pub trait Animation<V: Animatable>: Debug {
         b.sub(a).map(|r| r * V::cast_component(inverse_delta))
     }
 
-    // Highly sensitive to sampling errors in velocity
+    // Differences the velocity between samples, so it's highly sensitive to
+    // sampling errors unless the velocity is analytic
     fn acceleration(&self, sample_count: usize, sample_duration: Duration) -> Vec<V> {
         self.velocity(sample_count + 1, sample_duration)
             .windows(2)
@@ -160,6 +327,20 @@ pub trait BoundedAnimation<V: Animatable>: Animation<V> {
         (elapsed.as_secs_f64() / self.duration().as_secs_f64()).clamp(0.0, 1.0)
     }
 
+    /// Samples the animation at a percentage of its duration, rather than at a
+    /// specific duration.
+    ///
+    /// `progress` is clamped to the range `0.0..=1.0`, and NaN or infinite
+    /// progress samples the start.
+    fn sample_progress(&self, progress: f64) -> V {
+        let progress = if progress.is_finite() {
+            progress.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        self.sample(self.duration().mul_f64(progress))
+    }
+
     #[cfg(feature = "d6")]
     fn sample_random(&self) -> V {
         self.sample(d6::range(Duration::ZERO..=self.duration()))
@@ -213,6 +394,33 @@ pub trait BoundedAnimation<V: Animatable>: Animation<V> {
     {
         self.clone().chain(self.rev())
     }
+
+    /// Samples this animation at the normalized progress (`0.0` is the start
+    /// and `1.0` is the end) given by another animation.
+    ///
+    /// This is like [`Animation::remap`], but independent of this animation's
+    /// duration. Progress is handled like in
+    /// [`BoundedAnimation::sample_progress`].
+    fn remap_progress<T>(self, progress: T) -> RemapProgress<Self, T, V>
+    where
+        Self: Sized,
+        T: Animation<f64>,
+    {
+        RemapProgress::new(self, progress)
+    }
+
+    /// Binds this animation to an arbitrary input range, such as a scroll
+    /// offset or drag distance.
+    ///
+    /// An input of `input_start` samples the start of the animation, and an
+    /// input of `input_end` samples the end. `input_end` may be smaller than
+    /// `input_start`. `mode` specifies what happens outside of that range.
+    fn scrub(self, input_start: f64, input_end: f64, mode: ScrubMode) -> Scrub<Self, V>
+    where
+        Self: Sized,
+    {
+        Scrub::new(self, input_start, input_end, mode)
+    }
 }
 
 // impl<F, V> Animation<V> for F
@@ -233,6 +441,10 @@ where
     fn sample(&self, elapsed: Duration) -> V {
         A::sample(&*self, elapsed)
     }
+
+    fn sample_derivative(&self, elapsed: Duration) -> Option<V> {
+        A::sample_derivative(&**self, elapsed)
+    }
 }
 
 impl<A, V> BoundedAnimation<V> for Box<A>
@@ -244,3 +456,27 @@ where
         A::duration(&*self)
     }
 }
+
+impl<A, V> Animation<V> for Rc<A>
+where
+    A: Animation<V> + ?Sized,
+    V: Animatable,
+{
+    fn sample(&self, elapsed: Duration) -> V {
+        A::sample(&**self, elapsed)
+    }
+
+    fn sample_derivative(&self, elapsed: Duration) -> Option<V> {
+        A::sample_derivative(&**self, elapsed)
+    }
+}
+
+impl<A, V> BoundedAnimation<V> for Rc<A>
+where
+    A: BoundedAnimation<V> + ?Sized,
+    V: Animatable,
+{
+    fn duration(&self) -> Duration {
+        A::duration(&**self)
+    }
+}
@@ -0,0 +1,333 @@
+use crate::{Animatable, BoundedAnimation};
+use std::{
+    marker::PhantomData,
+    time::{Duration, Instant},
+};
+
+/// How many times a [`Player`] plays its animation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LoopMode {
+    /// Play once, and then stop.
+    Once,
+    /// Repeat forever.
+    Loop,
+    /// Play forwards and then backwards, forever.
+    PingPong,
+    /// Play the specified number of times, and then stop.
+    Times(u32),
+}
+
+/// Stateful playback of a [`BoundedAnimation`].
+///
+/// A player can either be driven by wall-clock time using
+/// [`Player::update`], or by explicit frame deltas using [`Player::advance`]
+/// (which is handy for deterministic tests).
+#[derive(Debug)]
+pub struct Player<A, V>
+where
+    A: BoundedAnimation<V>,
+    V: Animatable,
+{
+    anim: A,
+    // Position in seconds along the timeline with every repetition laid out
+    // end-to-end, so the first repetition is `0..duration`, the second is
+    // `duration..(2 * duration)`, etc.
+    head: f64,
+    rate: f64,
+    reversed: bool,
+    playing: bool,
+    loop_mode: LoopMode,
+    last_update: Option<Instant>,
+    _marker: PhantomData<V>,
+}
+
+impl<A, V> Player<A, V>
+where
+    A: BoundedAnimation<V>,
+    V: Animatable,
+{
+    /// Creates a paused player at the start of `anim`.
+    pub fn new(anim: A) -> Self {
+        Self {
+            anim,
+            head: 0.0,
+            rate: 1.0,
+            reversed: false,
+            playing: false,
+            loop_mode: LoopMode::Once,
+            last_update: None,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn with_loop_mode(mut self, loop_mode: LoopMode) -> Self {
+        self.set_loop_mode(loop_mode);
+        self
+    }
+
+    pub fn with_rate(mut self, rate: f64) -> Self {
+        self.set_rate(rate);
+        self
+    }
+
+    pub fn animation(&self) -> &A {
+        &self.anim
+    }
+
+    pub fn into_animation(self) -> A {
+        self.anim
+    }
+
+    /// Starts playing, restarting from the beginning if playback had finished.
+    ///
+    /// When reversed, the "beginning" is the end of the last repetition.
+    pub fn play(&mut self) {
+        if self.is_finished() {
+            self.head = if self.reversed {
+                self.total_duration().unwrap_or(0.0)
+            } else {
+                0.0
+            };
+        }
+        self.resume();
+    }
+
+    /// Stops advancing, keeping the current position.
+    pub fn pause(&mut self) {
+        self.playing = false;
+        self.last_update = None;
+    }
+
+    /// Continues playing from the current position.
+    pub fn resume(&mut self) {
+        self.playing = !self.is_finished();
+    }
+
+    /// Jumps to a position on the timeline.
+    ///
+    /// Positions beyond the animation's duration seek into later repetitions,
+    /// and are clamped to the end of the last repetition.
+    pub fn seek(&mut self, position: Duration) {
+        self.head = self.clamp_head(position.as_secs_f64());
+    }
+
+    /// Sets the playback speed, where `1.0` is normal speed.
+    ///
+    /// # Panics
+    /// If `rate` is negative or not finite. To play backwards, see
+    /// [`Player::reverse`].
+    pub fn set_rate(&mut self, rate: f64) {
+        assert!(
+            rate.is_finite() && rate >= 0.0,
+            "playback rate must be non-negative and finite, but you specified {}",
+            rate
+        );
+        self.rate = rate;
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Flips the direction of playback.
+    ///
+    /// Reversing after playback finishes and then resuming plays back towards
+    /// the start.
+    pub fn reverse(&mut self) {
+        self.reversed = !self.reversed;
+    }
+
+    pub fn is_reversed(&self) -> bool {
+        self.reversed
+    }
+
+    pub fn set_loop_mode(&mut self, loop_mode: LoopMode) {
+        self.loop_mode = loop_mode;
+        self.head = self.clamp_head(self.head);
+    }
+
+    pub fn loop_mode(&self) -> LoopMode {
+        self.loop_mode
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// If playback has reached the end (or the start, when reversed).
+    ///
+    /// This is never `true` for [`LoopMode::Loop`] or [`LoopMode::PingPong`].
+    pub fn is_finished(&self) -> bool {
+        self.total_duration()
+            .map(|total| {
+                if self.reversed {
+                    self.head <= 0.0
+                } else {
+                    self.head >= total
+                }
+            })
+            .unwrap_or(false)
+    }
+
+    /// Advances playback by wall-clock time.
+    ///
+    /// The first update after playing or resuming only records `now`.
+    pub fn update(&mut self, now: Instant) {
+        if let Some(last_update) = self.last_update {
+            self.advance(now.saturating_duration_since(last_update));
+        }
+        self.last_update = if self.playing { Some(now) } else { None };
+    }
+
+    /// Advances playback by an explicit frame delta.
+    pub fn advance(&mut self, delta: Duration) {
+        if self.playing {
+            let delta = delta.as_secs_f64() * self.rate;
+            let head = if self.reversed {
+                self.head - delta
+            } else {
+                self.head + delta
+            };
+            self.head = self.clamp_head(head);
+            if self.is_finished() {
+                self.pause();
+            }
+        }
+    }
+
+    /// Which repetition is playing, starting from zero.
+    pub fn iteration(&self) -> u32 {
+        let duration = self.anim.duration().as_secs_f64();
+        if duration == 0.0 || self.head <= 0.0 {
+            0
+        } else if self.is_finished() {
+            // The end of the last repetition belongs to that repetition
+            (self.head / duration).ceil() as u32 - 1
+        } else {
+            (self.head / duration).floor() as u32
+        }
+    }
+
+    /// The current position within the animation.
+    pub fn position(&self) -> Duration {
+        let duration = self.anim.duration().as_secs_f64();
+        if duration == 0.0 {
+            return Duration::ZERO;
+        }
+        let position = match self.loop_mode {
+            LoopMode::Once => self.head,
+            LoopMode::Times(times) => {
+                if times > 0 && self.head >= duration * f64::from(times) {
+                    // Finishing holds the last value, rather than wrapping back
+                    // to the start
+                    duration
+                } else {
+                    self.head.rem_euclid(duration)
+                }
+            }
+            LoopMode::Loop => self.head.rem_euclid(duration),
+            LoopMode::PingPong => {
+                let position = self.head.rem_euclid(2.0 * duration);
+                if position > duration {
+                    2.0 * duration - position
+                } else {
+                    position
+                }
+            }
+        };
+        Duration::from_secs_f64(position.clamp(0.0, duration))
+    }
+
+    /// The current position within the animation, as a percentage of its
+    /// duration.
+    pub fn progress(&self) -> f64 {
+        self.anim.percent_elapsed(self.position())
+    }
+
+    /// Samples the animation at the current position.
+    pub fn sample(&self) -> V {
+        self.anim.sample(self.position())
+    }
+
+    // The length of the timeline with every repetition laid out, or `None` if
+    // it repeats forever
+    fn total_duration(&self) -> Option<f64> {
+        let duration = self.anim.duration().as_secs_f64();
+        match self.loop_mode {
+            LoopMode::Once => Some(duration),
+            LoopMode::Times(times) => Some(duration * f64::from(times)),
+            LoopMode::Loop | LoopMode::PingPong => None,
+        }
+    }
+
+    fn clamp_head(&self, head: f64) -> f64 {
+        self.total_duration()
+            .map(|total| head.clamp(0.0, total))
+            .unwrap_or(head)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Frame, Interval};
+
+    fn player(loop_mode: LoopMode) -> Player<Interval<f64>, f64> {
+        let anim = Interval::linear(
+            Frame::new(Duration::from_secs_f64(0.0), 0.0),
+            Frame::new(Duration::from_secs_f64(2.0), 1.0),
+        );
+        let mut player = Player::new(anim).with_loop_mode(loop_mode);
+        player.play();
+        player
+    }
+
+    fn assert_approx(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_once() {
+        let mut player = player(LoopMode::Once);
+        player.advance(Duration::from_secs_f64(1.0));
+        assert_approx(player.sample(), 0.5);
+        player.pause();
+        player.advance(Duration::from_secs_f64(1.0));
+        assert_approx(player.sample(), 0.5);
+        player.resume();
+        player.advance(Duration::from_secs_f64(5.0));
+        assert_approx(player.sample(), 1.0);
+        assert!(player.is_finished());
+        assert!(!player.is_playing());
+
+        player.reverse();
+        player.resume();
+        player.advance(Duration::from_secs_f64(0.5));
+        assert_approx(player.sample(), 0.75);
+    }
+
+    #[test]
+    fn test_times() {
+        let mut player = player(LoopMode::Times(2)).with_rate(2.0);
+        player.advance(Duration::from_secs_f64(1.5));
+        assert_eq!(player.iteration(), 1);
+        assert_approx(player.sample(), 0.5);
+        player.advance(Duration::from_secs_f64(1.0));
+        assert!(player.is_finished());
+        assert_eq!(player.iteration(), 1);
+        assert_approx(player.sample(), 1.0);
+    }
+
+    #[test]
+    fn test_ping_pong() {
+        let mut player = player(LoopMode::PingPong);
+        player.advance(Duration::from_secs_f64(3.0));
+        assert_approx(player.sample(), 0.5);
+        player.advance(Duration::from_secs_f64(2.0));
+        assert_approx(player.sample(), 0.5);
+        assert!(!player.is_finished());
+
+        player.seek(Duration::from_secs_f64(2.0));
+        assert_approx(player.sample(), 1.0);
+    }
+}
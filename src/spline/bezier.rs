@@ -57,6 +57,13 @@ fn lookup(ox: f64, oy: f64, ix: f64, iy: f64, t: f64) -> f64 {
     fixed_bezier(oy, iy, invert_fixed_bezier(ox, ix, t))
 }
 
+// Exact d(out)/dt of `cubic_bezier_ease` for t in [0...1], using
+// dy/dx = (dy/ds) / (dx/ds)
+pub fn dt_cubic_bezier_ease(ox: f64, oy: f64, ix: f64, iy: f64, t: f64) -> f64 {
+    let s = invert_fixed_bezier(ox, ix, t);
+    dt_fixed_bezier(oy, iy, s) / dt_fixed_bezier(ox, ix, s)
+}
+
 fn square(x: f64) -> f64 {
     x * x
 }
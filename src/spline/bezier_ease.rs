@@ -1,13 +1,14 @@
-use super::bezier::cubic_bezier_ease;
+use super::bezier::{cubic_bezier_ease, dt_cubic_bezier_ease};
 use crate::ease::Ease;
 use gee::Point;
+use serde::{Deserialize, Serialize};
 
 // Describes the temporal Bezier ease between two Animatables
 // as a relative curve from (0, 0) to (1, 1).
 //
 // X values always range [0...1]
 // Y values usually range [0...1]
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
 pub struct BezierEase {
     pub ox: f64,
     pub oy: f64,
@@ -49,6 +50,11 @@ impl BezierEase {
     pub fn ease(&self, t: f64) -> f64 {
         cubic_bezier_ease(self.ox, self.oy, self.ix, self.iy, t.clamp(0.0, 1.0))
     }
+
+    /// The slope of the ease at `t`.
+    pub fn derivative(&self, t: f64) -> f64 {
+        dt_cubic_bezier_ease(self.ox, self.oy, self.ix, self.iy, t.clamp(0.0, 1.0))
+    }
 }
 
 #[cfg(test)]
@@ -57,6 +63,7 @@ mod tests {
     use crate::{
         interval::Interval, interval_track::IntervalTrack, lerp_components, lerp_scalar,
         spline::bezier_path::BezierPath, spline::SplineMap, Animatable, Animation as _,
+        ComponentWise as _,
     };
     use gee::en::Num as _;
     use std::time::Duration;
@@ -311,4 +318,48 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_vector_eased_path_derivative() {
+        let start = Duration::from_secs_f64(10.0);
+        let end = Duration::from_secs_f64(20.0);
+
+        let from: gee::Point<f64> = gee::Point::new(-4.0, 0.0);
+        let to: gee::Point<f64> = gee::Point::new(4.0, 0.0);
+
+        let b1: gee::Point<f64> = gee::Point::new(-4.0, -4.0);
+        let b2: gee::Point<f64> = gee::Point::new(4.0, 4.0);
+
+        let interval = Interval {
+            start,
+            end,
+            from,
+            to,
+            ease: Some(BezierEase::new_ease(0.5, 0.0, 0.5, 1.0)),
+            path: Some(BezierPath { b1, b2 }),
+            reticulated_spline: Some(SplineMap::from_bezier(&from, &b1, &b2, &to, true)),
+        };
+
+        let track = IntervalTrack::new()
+            .with_interval(interval)
+            .with_track_ease(Some(BezierEase::ease_in_out()));
+
+        // Analytic derivative should match central differences
+        let delta = Duration::from_secs_f64(1e-6);
+        let steps: usize = 100;
+        for i in 1..steps {
+            let t = start + (end - start).mul_f64(i.to_f64() / steps.to_f64());
+            let derivative = track.sample_derivative(t).unwrap();
+            let difference = track
+                .sample(t + delta)
+                .zip_map(track.sample(t - delta), |a, b| {
+                    (a - b) / (2.0 * delta.as_secs_f64())
+                });
+            assert!(
+                approx_eq_point(derivative, difference, TOLERANCE_LOOSE * 10.0),
+                "unequal derivative at {}",
+                i
+            );
+        }
+    }
 }
@@ -1,12 +1,13 @@
 use super::bezier::cubic_bezier;
 use crate::Animatable;
+use serde::{Deserialize, Serialize};
 
 // Describes the two middle control points for a bezier path
 // between an interval's spatial endpoints.
 //
 // These are in absolute coordinates,
 // i.e. (from, b1, b2, to) is a bezier.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct BezierPath<V: Animatable> {
     pub b1: V,
     pub b2: V,
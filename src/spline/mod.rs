@@ -7,12 +7,13 @@ use self::{bezier::dt_cubic_bezier, catmull_rom::catmull_rom_value};
 pub use self::{bezier_ease::*, bezier_path::*};
 use crate::{lerp::linear_value, Animatable};
 use gee::en::num_traits::Zero as _;
+use serde::{Deserialize, Serialize};
 
 // Spline polyline subdivision
 const SPLINE_SUBDIVISION: usize = 64;
 
 // Map from time to distance
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SplineMap {
     // Animatable always lerps using f64, and distance is always an f64
     pub steps: Vec<(f64, f64)>,
@@ -60,6 +61,17 @@ pub fn spline_ease(spline_map: &SplineMap, t: f64) -> f64 {
         .unwrap_or(t)
 }
 
+// Slope of `spline_ease`, given the speed along the spline (with respect to
+// spline time) at the eased point. Arc length is linear in eased time, so a
+// rectified map's slope is just the inverse of the spline's speed.
+pub fn dt_spline_ease(spline_map: &SplineMap, speed: f64) -> f64 {
+    if spline_map.rectify && speed != 0.0 {
+        spline_map.length / speed
+    } else {
+        1.0
+    }
+}
+
 // Find index for lookup in spline map with binary search.
 // Returns last index with d < distance.
 pub fn find_index(spline_map: &SplineMap, distance: f64) -> usize {
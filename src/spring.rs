@@ -0,0 +1,447 @@
+use crate::{Animatable, Animation, BoundedAnimation};
+use gee::en::Num as _;
+use std::{f64::consts::TAU, time::Duration};
+
+/// The physical parameters of a [`Spring`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpringConfig {
+    pub stiffness: f64,
+    pub damping: f64,
+    pub mass: f64,
+}
+
+impl Default for SpringConfig {
+    fn default() -> Self {
+        Self::from_response(0.55, 0.825)
+    }
+}
+
+impl SpringConfig {
+    /// # Panics
+    /// If any of the parameters aren't positive and finite. (A spring without
+    /// damping would never settle!)
+    pub fn new(stiffness: f64, damping: f64, mass: f64) -> Self {
+        for (name, value) in [
+            ("stiffness", stiffness),
+            ("damping", damping),
+            ("mass", mass),
+        ] {
+            assert!(
+                value.is_finite() && value > 0.0,
+                "spring {} must be positive and finite, but you specified {}",
+                name,
+                value
+            );
+        }
+        Self {
+            stiffness,
+            damping,
+            mass,
+        }
+    }
+
+    /// Creates a spring with unit mass from more intuitive parameters.
+    ///
+    /// `response` is the period in seconds of the spring if it were undamped,
+    /// which roughly controls how quickly it reaches its target.
+    /// `damping_ratio` controls how much the spring oscillates: `1.0` is
+    /// critically damped (the fastest the spring can settle without
+    /// overshooting), smaller values bounce, and larger values are sluggish.
+    pub fn from_response(response: f64, damping_ratio: f64) -> Self {
+        let angular_frequency = TAU / response;
+        Self::new(
+            angular_frequency * angular_frequency,
+            2.0 * damping_ratio * angular_frequency,
+            1.0,
+        )
+    }
+
+    /// Like [`SpringConfig::from_response`], but returns `None` rather than
+    /// panicking if the parameters are out of range, including when they're
+    /// so large or small that the stiffness or damping overflows.
+    pub fn try_from_response(response: f64, damping_ratio: f64) -> Option<Self> {
+        let angular_frequency = TAU / response;
+        let (stiffness, damping) = (
+            angular_frequency * angular_frequency,
+            2.0 * damping_ratio * angular_frequency,
+        );
+        if [stiffness, damping]
+            .iter()
+            .all(|value| value.is_finite() && *value > 0.0)
+        {
+            Some(Self::new(stiffness, damping, 1.0))
+        } else {
+            None
+        }
+    }
+
+    /// The angular frequency the spring would oscillate at if it were undamped.
+    pub fn natural_frequency(&self) -> f64 {
+        (self.stiffness / self.mass).sqrt()
+    }
+
+    pub fn damping_ratio(&self) -> f64 {
+        self.damping / (2.0 * (self.stiffness * self.mass).sqrt())
+    }
+}
+
+// The motion of a damped spring can be written as
+// `x(t) = target + displacement * a(t) + velocity * b(t)`, where `a` and `b`
+// only depend on the spring's parameters.
+#[derive(Clone, Copy, Debug)]
+enum Solution {
+    Underdamped { decay: f64, frequency: f64 },
+    Critical { frequency: f64 },
+    Overdamped { r1: f64, r2: f64 },
+}
+
+impl Solution {
+    fn new(config: SpringConfig) -> Self {
+        let frequency = config.natural_frequency();
+        let ratio = config.damping_ratio();
+        if (ratio - 1.0).abs() < 1e-9 {
+            Self::Critical { frequency }
+        } else if ratio < 1.0 {
+            Self::Underdamped {
+                decay: ratio * frequency,
+                frequency: frequency * (1.0 - ratio * ratio).sqrt(),
+            }
+        } else {
+            let root = (ratio * ratio - 1.0).sqrt();
+            Self::Overdamped {
+                r1: -frequency * (ratio - root),
+                r2: -frequency * (ratio + root),
+            }
+        }
+    }
+
+    // Returns `a`, `b` and their derivatives at `t`
+    fn coefficients(&self, t: f64) -> [f64; 4] {
+        match *self {
+            Self::Underdamped { decay, frequency } => {
+                let envelope = (-decay * t).exp();
+                let (sin, cos) = (frequency * t).sin_cos();
+                let natural_sq = decay * decay + frequency * frequency;
+                [
+                    envelope * (cos + decay / frequency * sin),
+                    envelope * sin / frequency,
+                    -envelope * sin * natural_sq / frequency,
+                    envelope * (cos - decay / frequency * sin),
+                ]
+            }
+            Self::Critical { frequency } => {
+                let envelope = (-frequency * t).exp();
+                [
+                    envelope * (1.0 + frequency * t),
+                    envelope * t,
+                    -envelope * frequency * frequency * t,
+                    envelope * (1.0 - frequency * t),
+                ]
+            }
+            Self::Overdamped { r1, r2 } => {
+                let (e1, e2) = ((r1 * t).exp(), (r2 * t).exp());
+                let denom = r1 - r2;
+                [
+                    (r1 * e2 - r2 * e1) / denom,
+                    (e1 - e2) / denom,
+                    r1 * r2 * (e2 - e1) / denom,
+                    (r1 * e1 - r2 * e2) / denom,
+                ]
+            }
+        }
+    }
+
+    // Upper bounds for `|a|` and `|b|` at `t`
+    fn envelopes(&self, t: f64) -> (f64, f64) {
+        match *self {
+            Self::Underdamped { decay, frequency } => {
+                let envelope = (-decay * t).exp();
+                let natural = (decay * decay + frequency * frequency).sqrt();
+                (envelope * natural / frequency, envelope / frequency)
+            }
+            Self::Critical { .. } | Self::Overdamped { .. } => {
+                // Neither coefficient oscillates, so they're their own bounds
+                let [a, b, _, _] = self.coefficients(t);
+                (a.abs(), b.abs())
+            }
+        }
+    }
+
+    // The time after which the bounds from `envelopes` only decrease
+    fn peak(&self) -> f64 {
+        match *self {
+            Self::Underdamped { .. } => 0.0,
+            Self::Critical { frequency } => 1.0 / frequency,
+            Self::Overdamped { r1, r2 } => (r2 / r1).ln() / (r1 - r2),
+        }
+    }
+
+    // The time after which the distance from the target stays within
+    // `tolerance`, given the magnitude of the initial displacement and velocity
+    fn settle_time(&self, displacement: f64, velocity: f64, tolerance: f64) -> f64 {
+        let bound = |t: f64| {
+            let (a, b) = self.envelopes(t);
+            displacement * a + velocity * b
+        };
+        if displacement <= tolerance && velocity == 0.0 {
+            // Released from rest, the spring never gets further from its target
+            return 0.0;
+        }
+        // Both bounds only decrease after the peak
+        let mut lo = self.peak();
+        if bound(lo) <= tolerance {
+            return lo;
+        }
+        let mut hi = lo.max(1e-3);
+        while bound(hi) > tolerance {
+            lo = hi;
+            hi *= 2.0;
+        }
+        for _ in 0..64 {
+            let mid = 0.5 * (lo + hi);
+            if bound(mid) > tolerance {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        hi
+    }
+}
+
+// A spring with a response of one second, released from rest at `1.0` and
+// pulled towards `0.0`. Returns how long it takes to settle within
+// `Spring::DEFAULT_TOLERANCE`, which is used to normalize spring eases.
+pub(crate) fn unit_settle_time(damping_ratio: f64) -> f64 {
+    Solution::new(SpringConfig::from_response(1.0, damping_ratio)).settle_time(
+        1.0,
+        0.0,
+        Spring::<f64>::DEFAULT_TOLERANCE,
+    )
+}
+
+// The position and velocity at `t` of the spring described above.
+pub(crate) fn unit_motion(damping_ratio: f64, t: f64) -> (f64, f64) {
+    let [a, _, da, _] =
+        Solution::new(SpringConfig::from_response(1.0, damping_ratio)).coefficients(t);
+    (a, da)
+}
+
+/// A damped spring pulling a value towards a target.
+///
+/// The spring is solved analytically, so it can be sampled at any time
+/// without stepping a simulation. It's considered finished once it's settled
+/// within a tolerance of its target, after which it samples as exactly the
+/// target.
+#[derive(Clone, Debug)]
+pub struct Spring<V>
+where
+    V: Animatable,
+{
+    from: V,
+    target: V,
+    velocity: V,
+    config: SpringConfig,
+    solution: Solution,
+    tolerance: f64,
+    start: Duration,
+    settle: Duration,
+}
+
+impl<V> Animation<V> for Spring<V>
+where
+    V: Animatable,
+{
+    fn sample(&self, elapsed: Duration) -> V {
+        if elapsed >= self.duration() {
+            self.target
+        } else {
+            let [a, b, _, _] = self.coefficients(elapsed);
+            self.evaluate(a, b)
+        }
+    }
+
+    fn sample_derivative(&self, elapsed: Duration) -> Option<V> {
+        Some(self.velocity_at(elapsed))
+    }
+}
+
+impl<V> BoundedAnimation<V> for Spring<V>
+where
+    V: Animatable,
+{
+    fn duration(&self) -> Duration {
+        self.start.saturating_add(self.settle)
+    }
+}
+
+impl<V> Spring<V>
+where
+    V: Animatable,
+{
+    /// The default distance from the target that a spring is considered
+    /// settled within.
+    pub const DEFAULT_TOLERANCE: f64 = 1e-3;
+
+    /// Creates a spring starting at rest at `from`.
+    pub fn new(from: V, target: V, config: SpringConfig) -> Self {
+        let mut spring = Self {
+            from,
+            target,
+            velocity: from.sub(from),
+            config,
+            solution: Solution::new(config),
+            tolerance: Self::DEFAULT_TOLERANCE,
+            start: Duration::ZERO,
+            settle: Duration::ZERO,
+        };
+        spring.update_settle();
+        spring
+    }
+
+    /// Sets the initial velocity, in units per second.
+    pub fn with_velocity(mut self, velocity: V) -> Self {
+        self.velocity = velocity;
+        self.update_settle();
+        self
+    }
+
+    /// Sets how close the spring needs to be to its target to be considered
+    /// settled. Defaults to [`Self::DEFAULT_TOLERANCE`].
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        assert!(
+            tolerance.is_finite() && tolerance > 0.0,
+            "spring tolerance must be positive and finite, but you specified {}",
+            tolerance
+        );
+        self.tolerance = tolerance;
+        self.update_settle();
+        self
+    }
+
+    pub fn config(&self) -> SpringConfig {
+        self.config
+    }
+
+    pub fn target(&self) -> V {
+        self.target
+    }
+
+    /// The rate of change of the spring at `elapsed`, in units per second.
+    pub fn velocity_at(&self, elapsed: Duration) -> V {
+        if elapsed > self.duration() {
+            self.from.sub(self.from)
+        } else {
+            let [_, _, da, db] = self.coefficients(elapsed);
+            self.evaluate(da, db).sub(self.target)
+        }
+    }
+
+    /// Changes the target of the spring at `at`, continuing on from the
+    /// spring's current value and velocity.
+    ///
+    /// Since the spring only remembers its motion from `at` onwards, sampling
+    /// before `at` afterwards returns the value at `at`.
+    pub fn retarget(&mut self, at: Duration, target: V) {
+        let (from, velocity) = (self.sample(at), self.velocity_at(at));
+        self.from = from;
+        self.velocity = velocity;
+        self.target = target;
+        self.start = at;
+        self.update_settle();
+    }
+
+    fn coefficients(&self, elapsed: Duration) -> [f64; 4] {
+        self.solution
+            .coefficients(elapsed.saturating_sub(self.start).as_secs_f64())
+    }
+
+    // `target + (from - target) * a + velocity * b`
+    fn evaluate(&self, a: f64, b: f64) -> V {
+        self.target
+            .zip_map(self.from, |target, from| {
+                (target.to_f64() + (from.to_f64() - target.to_f64()) * a).cast()
+            })
+            .zip_map(self.velocity, |value, velocity| {
+                (value.to_f64() + velocity.to_f64() * b).cast()
+            })
+    }
+
+    fn update_settle(&mut self) {
+        let displacement = self.from.distance_to(self.target);
+        let velocity = self.velocity.distance_to(self.from.sub(self.from));
+        // A spring that takes too long to settle to even measure just never
+        // finishes
+        self.settle = Duration::try_from_secs_f64(self.solution.settle_time(
+            displacement,
+            velocity,
+            self.tolerance,
+        ))
+        .unwrap_or(Duration::MAX);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Checks the analytic solution against a finite difference
+    fn assert_consistent(spring: &Spring<f64>) {
+        let dt = 1e-6;
+        for i in 1..20 {
+            let t = (spring.start + spring.settle.mul_f64(i as f64 / 20.0)).as_secs_f64();
+            let numeric = (spring.sample(Duration::from_secs_f64(t + dt))
+                - spring.sample(Duration::from_secs_f64(t - dt)))
+                / (2.0 * dt);
+            let analytic = spring.velocity_at(Duration::from_secs_f64(t));
+            assert!(
+                (numeric - analytic).abs() < 1e-3 * analytic.abs().max(1.0),
+                "{} != {} at {}",
+                numeric,
+                analytic,
+                t
+            );
+        }
+    }
+
+    #[test]
+    fn test_damping() {
+        for ratio in [0.3, 1.0, 2.5] {
+            let spring = Spring::new(0.0, 100.0, SpringConfig::from_response(0.5, ratio))
+                .with_velocity(-50.0);
+            assert_eq!(spring.sample(Duration::ZERO), 0.0);
+            assert_eq!(spring.velocity_at(Duration::ZERO), -50.0);
+            assert_consistent(&spring);
+
+            // Settled values stay within tolerance
+            let settle = spring.duration().as_secs_f64();
+            for i in 0..100 {
+                let t = settle * (1.0 + i as f64 / 10.0);
+                let [a, b, _, _] = spring.coefficients(Duration::from_secs_f64(t));
+                let distance = (spring.evaluate(a, b) - 100.0).abs();
+                assert!(
+                    distance <= Spring::<f64>::DEFAULT_TOLERANCE + 1e-9,
+                    "{} at {}",
+                    distance,
+                    t
+                );
+            }
+            assert_eq!(spring.sample(spring.duration()), 100.0);
+        }
+    }
+
+    #[test]
+    fn test_retarget() {
+        let mut spring = Spring::new(0.0f64, 1.0, SpringConfig::default());
+        let (value, velocity) = (
+            spring.sample(Duration::from_secs_f64(0.1)),
+            spring.velocity_at(Duration::from_secs_f64(0.1)),
+        );
+        spring.retarget(Duration::from_secs_f64(0.1), -1.0);
+        assert!((spring.sample(Duration::from_secs_f64(0.1)) - value).abs() < 1e-9);
+        assert!((spring.velocity_at(Duration::from_secs_f64(0.1)) - velocity).abs() < 1e-9);
+        assert!(spring.duration() > Duration::from_secs_f64(0.1));
+        assert_eq!(spring.sample(spring.duration()), -1.0);
+        assert_consistent(&spring);
+    }
+}
@@ -0,0 +1,348 @@
+use crate::{Animatable, Animation, BoundedAnimation, Interrupt};
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug},
+    hash::Hash,
+    rc::Rc,
+    time::Duration,
+};
+
+#[derive(Debug)]
+struct State<V: Animatable> {
+    anim: Rc<dyn BoundedAnimation<V>>,
+    looping: bool,
+}
+
+impl<V: Animatable + 'static> State<V> {
+    fn percent_elapsed(&self, elapsed: Duration) -> f64 {
+        if self.anim.duration().is_zero() {
+            1.0
+        } else if self.looping {
+            (elapsed.as_secs_f64() / self.anim.duration().as_secs_f64()).fract()
+        } else {
+            self.anim.percent_elapsed(elapsed)
+        }
+    }
+
+    fn to_animation(&self) -> Box<dyn Animation<V>> {
+        if self.looping && !self.anim.duration().is_zero() {
+            Box::new(Rc::clone(&self.anim).cycle())
+        } else {
+            Box::new(Rc::clone(&self.anim))
+        }
+    }
+}
+
+type Condition<P> = Box<dyn Fn(&P, f64) -> bool>;
+
+/// A transition between two states of a [`StateMachine`].
+///
+/// By default, a transition is taken as soon as its source state finishes,
+/// which is never for looping states. Use [`Transition::when`] to specify a
+/// different condition.
+pub struct Transition<S, P> {
+    from: Option<S>,
+    to: S,
+    blend: Duration,
+    condition: Condition<P>,
+}
+
+impl<S: Debug, P> Debug for Transition<S, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Transition")
+            .field("from", &self.from)
+            .field("to", &self.to)
+            .field("blend", &self.blend)
+            .finish()
+    }
+}
+
+impl<S, P> Transition<S, P> {
+    /// A transition from `from` to `to`, blending between them for `blend`.
+    pub fn new(from: S, to: S, blend: Duration) -> Self {
+        Self {
+            from: Some(from),
+            ..Self::from_any(to, blend)
+        }
+    }
+
+    /// A transition from any other state to `to`.
+    pub fn from_any(to: S, blend: Duration) -> Self {
+        Self {
+            from: None,
+            to,
+            blend,
+            condition: Box::new(|_, percent_elapsed| percent_elapsed >= 1.0),
+        }
+    }
+
+    /// Only takes this transition when `condition` returns `true`.
+    ///
+    /// The condition is given the machine's parameters, along with how far
+    /// through the current state's animation playback is (see
+    /// [`StateMachine::normalized_time`]).
+    pub fn when(mut self, condition: impl Fn(&P, f64) -> bool + 'static) -> Self {
+        self.condition = Box::new(condition);
+        self
+    }
+}
+
+/// A transition that's currently being blended.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PendingTransition<'a, S> {
+    pub from: &'a S,
+    pub to: &'a S,
+    /// How far through the blend we are, from `0.0` to `1.0`.
+    pub progress: f64,
+}
+
+#[derive(Debug)]
+struct ActiveBlend<S> {
+    from: S,
+    start: Duration,
+    duration: Duration,
+}
+
+/// A set of states that each own an animation, along with conditional
+/// transitions between them.
+///
+/// Transitions are evaluated against parameters of type `P` whenever the
+/// machine is [updated](StateMachine::update), and hand off from the current
+/// animation to the next using [`Interrupt`], which preserves velocity.
+///
+/// Sampling the machine samples the current state (or blend) at an elapsed
+/// time relative to when the machine was created, which should line up with
+/// the times passed to [`StateMachine::update`].
+pub struct StateMachine<S, P, V>
+where
+    S: Clone + Debug + Eq + Hash,
+    V: Animatable,
+{
+    states: HashMap<S, State<V>>,
+    transitions: Vec<Transition<S, P>>,
+    current: S,
+    entered: Duration,
+    blend: Option<ActiveBlend<S>>,
+    now: Duration,
+    // The animation being played, including any ongoing blend
+    anim: Box<dyn Animation<V>>,
+}
+
+impl<S, P, V> Debug for StateMachine<S, P, V>
+where
+    S: Clone + Debug + Eq + Hash,
+    V: Animatable,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StateMachine")
+            .field("states", &self.states)
+            .field("transitions", &self.transitions)
+            .field("current", &self.current)
+            .field("entered", &self.entered)
+            .field("blend", &self.blend)
+            .field("now", &self.now)
+            .field("anim", &self.anim)
+            .finish()
+    }
+}
+
+impl<S, P, V> StateMachine<S, P, V>
+where
+    S: Clone + Debug + Eq + Hash,
+    V: Animatable + 'static,
+{
+    /// Creates a machine that starts in `initial`.
+    pub fn new(initial: S, anim: impl BoundedAnimation<V> + 'static, looping: bool) -> Self {
+        let state = State {
+            anim: Rc::new(anim),
+            looping,
+        };
+        let anim = state.to_animation();
+        Self {
+            states: std::iter::once((initial.clone(), state)).collect(),
+            transitions: vec![],
+            current: initial,
+            entered: Duration::ZERO,
+            blend: None,
+            now: Duration::ZERO,
+            anim,
+        }
+    }
+
+    pub fn with_state(
+        mut self,
+        state: S,
+        anim: impl BoundedAnimation<V> + 'static,
+        looping: bool,
+    ) -> Self {
+        self.add_state(state, anim, looping);
+        self
+    }
+
+    pub fn with_transition(mut self, transition: Transition<S, P>) -> Self {
+        self.add_transition(transition);
+        self
+    }
+
+    /// Adds a state, replacing any existing state with the same name.
+    ///
+    /// Replacing the current state doesn't affect playback until the state is
+    /// entered again.
+    pub fn add_state(
+        &mut self,
+        state: S,
+        anim: impl BoundedAnimation<V> + 'static,
+        looping: bool,
+    ) -> &mut Self {
+        self.states.insert(
+            state,
+            State {
+                anim: Rc::new(anim),
+                looping,
+            },
+        );
+        self
+    }
+
+    /// Adds a transition. When multiple transitions could be taken, the one
+    /// added first wins.
+    pub fn add_transition(&mut self, transition: Transition<S, P>) -> &mut Self {
+        self.transitions.push(transition);
+        self
+    }
+
+    pub fn current_state(&self) -> &S {
+        &self.current
+    }
+
+    /// The transition being blended, if any.
+    pub fn pending_transition(&self) -> Option<PendingTransition<'_, S>> {
+        self.blend.as_ref().map(|blend| PendingTransition {
+            from: &blend.from,
+            to: &self.current,
+            progress: if blend.duration.is_zero() {
+                1.0
+            } else {
+                ((self.now.saturating_sub(blend.start)).as_secs_f64()
+                    / blend.duration.as_secs_f64())
+                .min(1.0)
+            },
+        })
+    }
+
+    /// How far through the current state's animation playback is, from `0.0`
+    /// to `1.0`. Looping states wrap back around to `0.0`.
+    pub fn normalized_time(&self) -> f64 {
+        self.states[&self.current].percent_elapsed(self.now.saturating_sub(self.entered))
+    }
+
+    /// Takes the first transition out of the current state whose condition
+    /// holds, and finishes any blend that's complete.
+    pub fn update(&mut self, now: Duration, params: &P) {
+        self.now = now;
+        if matches!(self.pending_transition(), Some(pending) if pending.progress >= 1.0) {
+            self.blend = None;
+        }
+        let normalized_time = self.normalized_time();
+        let next = self
+            .transitions
+            .iter()
+            .filter(|transition| {
+                transition.to != self.current
+                    && *transition.from.as_ref().unwrap_or(&self.current) == self.current
+            })
+            .find(|transition| (transition.condition)(params, normalized_time))
+            .map(|transition| (transition.to.clone(), transition.blend));
+        if let Some((to, blend)) = next {
+            self.transition_to(to, blend);
+        }
+    }
+
+    /// Immediately starts a transition to `state`, regardless of any
+    /// transitions that were added.
+    ///
+    /// # Panics
+    /// If `state` was never added.
+    pub fn transition_to(&mut self, state: S, blend: Duration) {
+        let next = self
+            .states
+            .get(&state)
+            .unwrap_or_else(|| panic!("no state named {:?}", state))
+            .to_animation();
+        let now = self.now;
+        replace_with::replace_with_or_abort(&mut self.anim, |prev| {
+            Box::new(Interrupt::new(prev, next, now, blend))
+        });
+        self.blend = Some(ActiveBlend {
+            from: std::mem::replace(&mut self.current, state),
+            start: self.now,
+            duration: blend,
+        });
+        self.entered = self.now;
+    }
+}
+
+impl<S, P, V> Animation<V> for StateMachine<S, P, V>
+where
+    S: Clone + Debug + Eq + Hash,
+    V: Animatable,
+{
+    fn sample(&self, elapsed: Duration) -> V {
+        self.anim.sample(elapsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Frame, Interval};
+
+    fn machine() -> StateMachine<&'static str, bool, f64> {
+        StateMachine::new(
+            "idle",
+            Interval::hold(0.0, Duration::from_secs_f64(1.0)),
+            true,
+        )
+        .with_state(
+            "jump",
+            Interval::linear(
+                Frame::new(Duration::from_secs_f64(0.0), 0.0),
+                Frame::new(Duration::from_secs_f64(1.0), 1.0),
+            ),
+            false,
+        )
+        .with_transition(
+            Transition::new("idle", "jump", Duration::from_secs_f64(0.5))
+                .when(|jumping, _| *jumping),
+        )
+        .with_transition(Transition::new(
+            "jump",
+            "idle",
+            Duration::from_secs_f64(0.0),
+        ))
+    }
+
+    #[test]
+    fn test_transitions() {
+        let mut machine = machine();
+        machine.update(Duration::from_secs_f64(1.5), &false);
+        assert_eq!(*machine.current_state(), "idle");
+        assert!((machine.normalized_time() - 0.5).abs() < 1e-9);
+
+        machine.update(Duration::from_secs_f64(2.0), &true);
+        assert_eq!(*machine.current_state(), "jump");
+        let pending = machine.pending_transition().unwrap();
+        assert_eq!((*pending.from, *pending.to), ("idle", "jump"));
+        assert_eq!(pending.progress, 0.0);
+
+        machine.update(Duration::from_secs_f64(2.25), &true);
+        assert_eq!(machine.pending_transition().unwrap().progress, 0.5);
+        machine.update(Duration::from_secs_f64(2.5), &true);
+        assert!(machine.pending_transition().is_none());
+        assert!((machine.sample(Duration::from_secs_f64(2.5)) - 0.5).abs() < 1e-9);
+
+        // One-shot states transition out once they finish
+        machine.update(Duration::from_secs_f64(3.0), &true);
+        assert_eq!(*machine.current_state(), "idle");
+    }
+}
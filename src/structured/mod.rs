@@ -1,6 +1,7 @@
 pub mod affine;
 pub mod clock;
 pub mod flip;
+pub mod overlay;
 pub mod path;
 pub mod radial;
 pub mod retarget;
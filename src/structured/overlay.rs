@@ -0,0 +1,21 @@
+// This macro generates the code required for layering an additive animation
+// on top of an existing animation.
+//
+// Simply add to any struct with members that are boxed Animations:
+// overlayable!([member_identifier], [animatable_type]);
+
+#[macro_export]
+macro_rules! overlayable {
+    ( $anim:ident, $v:ty ) => {
+        paste::paste! {
+            pub fn [<$anim _overlay>](
+                &mut self,
+                overlay: Box<dyn Animation<$v>>,
+                relative: bool,
+            ) {
+                self.$anim
+                    .replace_with(|anim| Box::new(anim.additive(overlay, relative)));
+            }
+        }
+    };
+}
@@ -3,7 +3,7 @@ use crate::{
     ease::Ease,
     interval::Interval,
     interval_track::{BookendStyle, IntervalTrack},
-    retargetable, Animation,
+    overlayable, retargetable, Animation,
 };
 use gee::{en::Num, Angle, Point, Transform};
 use std::{f64::consts::TAU, time::Duration};
@@ -108,6 +108,9 @@ impl PathAnimation {
 
     retargetable!(position, Animation, Point<f32>);
     retargetable!(angle, Animation, Angle<f32>);
+
+    overlayable!(position, Point<f32>);
+    overlayable!(angle, Angle<f32>);
 }
 
 impl Animation<Point<f32>> for PathAnimation {
@@ -1,5 +1,5 @@
 use crate::{
-    constant::Constant, ease::Ease, interval::Interval, retargetable,
+    constant::Constant, ease::Ease, interval::Interval, overlayable, retargetable,
     spline::bezier_ease::BezierEase, Animation, BoundedAnimation,
 };
 use gee::{Angle, Circle, Point};
@@ -88,6 +88,10 @@ impl Radial {
     retargetable!(distance, Animation, f32);
     retargetable!(origin, Animation, Point<f32>);
     retargetable!(angle, Animation, Angle<f32>);
+
+    overlayable!(distance, f32);
+    overlayable!(origin, Point<f32>);
+    overlayable!(angle, Angle<f32>);
 }
 
 impl Animation<Point<f32>> for Radial {
@@ -0,0 +1,352 @@
+use crate::{Animatable, BoundedAnimation};
+use std::{
+    any::Any,
+    fmt::{self, Debug},
+    time::Duration,
+};
+
+/// A named point in time on a [`Timeline`].
+#[derive(Clone, Debug)]
+pub struct Marker {
+    pub name: String,
+    pub time: Duration,
+}
+
+/// Something on a [`Timeline`] that can be crossed during playback.
+#[derive(Debug)]
+pub enum Cue<'a, E> {
+    Marker(&'a str),
+    Event(&'a E),
+}
+
+// Derived impls would needlessly require `E: Clone`
+impl<'a, E> Clone for Cue<'a, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, E> Copy for Cue<'a, E> {}
+
+/// A [`Cue`] that was crossed between two samples of a [`Timeline`].
+#[derive(Debug)]
+pub struct Crossing<'a, E> {
+    /// The time of the cue, relative to the start of the timeline.
+    pub time: Duration,
+    /// Which repetition of the timeline the cue was crossed in. This is always
+    /// zero unless the timeline loops.
+    pub cycle: u32,
+    pub cue: Cue<'a, E>,
+}
+
+struct TimelineTrack {
+    name: String,
+    type_name: &'static str,
+    duration: Duration,
+    // Always a `Box<dyn BoundedAnimation<V>>` for the `V` named by `type_name`
+    anim: Box<dyn Any>,
+}
+
+impl Debug for TimelineTrack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimelineTrack")
+            .field("name", &self.name)
+            .field("type_name", &self.type_name)
+            .field("duration", &self.duration)
+            .finish()
+    }
+}
+
+/// A collection of named tracks, along with named markers and typed events
+/// that can be used to trigger things in sync with the animation.
+#[derive(Debug)]
+pub struct Timeline<E = ()> {
+    tracks: Vec<TimelineTrack>,
+    markers: Vec<Marker>,
+    events: Vec<(Duration, E)>,
+    duration: Option<Duration>,
+    looping: bool,
+}
+
+impl<E> Default for Timeline<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> Timeline<E> {
+    pub fn new() -> Self {
+        Self {
+            tracks: vec![],
+            markers: vec![],
+            events: vec![],
+            duration: None,
+            looping: false,
+        }
+    }
+
+    /// Overrides the duration of the timeline, which otherwise lasts until
+    /// the end of its last track, marker or event.
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Makes the timeline repeat forever, like [`BoundedAnimation::cycle`].
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    pub fn with_track<V, A>(mut self, name: impl Into<String>, anim: A) -> Self
+    where
+        V: Animatable + 'static,
+        A: BoundedAnimation<V> + 'static,
+    {
+        self.add_track(name, anim);
+        self
+    }
+
+    pub fn with_marker(mut self, name: impl Into<String>, time: Duration) -> Self {
+        self.add_marker(name, time);
+        self
+    }
+
+    pub fn with_event(mut self, time: Duration, event: E) -> Self {
+        self.add_event(time, event);
+        self
+    }
+
+    /// Adds a track, replacing any existing track with the same name.
+    pub fn add_track<V, A>(&mut self, name: impl Into<String>, anim: A) -> &mut Self
+    where
+        V: Animatable + 'static,
+        A: BoundedAnimation<V> + 'static,
+    {
+        let name = name.into();
+        self.tracks.retain(|track| track.name != name);
+        let anim: Box<dyn BoundedAnimation<V>> = Box::new(anim);
+        self.tracks.push(TimelineTrack {
+            name,
+            type_name: std::any::type_name::<V>(),
+            duration: anim.duration(),
+            anim: Box::new(anim),
+        });
+        self
+    }
+
+    /// Adds a marker, replacing any existing marker with the same name.
+    pub fn add_marker(&mut self, name: impl Into<String>, time: Duration) -> &mut Self {
+        let name = name.into();
+        self.markers.retain(|marker| marker.name != name);
+        let index = self.markers.partition_point(|marker| marker.time <= time);
+        self.markers.insert(index, Marker { name, time });
+        self
+    }
+
+    pub fn add_event(&mut self, time: Duration, event: E) -> &mut Self {
+        let index = self.events.partition_point(|(t, _)| *t <= time);
+        self.events.insert(index, (time, event));
+        self
+    }
+
+    pub fn markers(&self) -> &[Marker] {
+        &self.markers
+    }
+
+    /// The time of the marker with the given name.
+    pub fn marker(&self, name: &str) -> Option<Duration> {
+        self.markers
+            .iter()
+            .find(|marker| marker.name == name)
+            .map(|marker| marker.time)
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.duration.unwrap_or_else(|| {
+            self.tracks
+                .iter()
+                .map(|track| track.duration)
+                .chain(self.markers.iter().map(|marker| marker.time))
+                .chain(self.events.iter().map(|(time, _)| *time))
+                .max()
+                .unwrap_or_default()
+        })
+    }
+
+    fn looping_duration(&self) -> Option<Duration> {
+        Some(self.duration()).filter(|duration| self.looping && !duration.is_zero())
+    }
+
+    /// Converts an elapsed duration into a time relative to the start of the
+    /// timeline, accounting for looping.
+    pub fn local_time(&self, elapsed: Duration) -> Duration {
+        self.looping_duration()
+            .map(|duration| Duration::from_nanos((elapsed.as_nanos() % duration.as_nanos()) as u64))
+            .unwrap_or(elapsed)
+    }
+
+    /// Samples the track with the given name.
+    ///
+    /// Returns `None` if there's no track with that name, or if the track
+    /// doesn't animate values of type `V`.
+    pub fn sample_track<V>(&self, name: &str, elapsed: Duration) -> Option<V>
+    where
+        V: Animatable + 'static,
+    {
+        let local_time = self.local_time(elapsed);
+        self.tracks
+            .iter()
+            .find(|track| track.name == name)
+            .and_then(|track| track.anim.downcast_ref::<Box<dyn BoundedAnimation<V>>>())
+            .map(|anim| anim.sample(local_time))
+    }
+
+    /// Every marker and event crossed when moving from `prev` to `now`, in the
+    /// order they were crossed.
+    ///
+    /// When playing forwards (`now > prev`), cues at exactly `prev` are
+    /// included, while cues at exactly `now` aren't. This is reversed when
+    /// playing backwards, so that consecutive calls never report a cue twice.
+    /// If the timeline loops, then cues are reported once per repetition.
+    pub fn crossed(&self, prev: Duration, now: Duration) -> Vec<Crossing<'_, E>> {
+        let reverse = now < prev;
+        let (min, max) = if reverse { (now, prev) } else { (prev, now) };
+        let is_crossed = |absolute: Duration| {
+            if reverse {
+                min < absolute && absolute <= max
+            } else {
+                min <= absolute && absolute < max
+            }
+        };
+
+        let cues = self
+            .markers
+            .iter()
+            .map(|marker| (marker.time, Cue::Marker(marker.name.as_str())))
+            .chain(
+                self.events
+                    .iter()
+                    .map(|(time, event)| (*time, Cue::Event(event))),
+            );
+
+        let mut crossings: Vec<_> = match self.looping_duration() {
+            Some(duration) => {
+                let cycle_of =
+                    |elapsed: Duration| (elapsed.as_nanos() / duration.as_nanos()) as u32;
+                // A cue at the very end of one cycle is at the same time as the
+                // start of the next, so the previous cycle is checked too
+                let cycles = cycle_of(min).saturating_sub(1)..=cycle_of(max);
+                cues.flat_map(|(time, cue)| {
+                    cycles
+                        .clone()
+                        .filter(move |cycle| is_crossed(duration * *cycle + time))
+                        .map(move |cycle| Crossing { time, cycle, cue })
+                })
+                .collect()
+            }
+            None => cues
+                .filter(|(time, _)| is_crossed(*time))
+                .map(|(time, cue)| Crossing {
+                    time,
+                    cycle: 0,
+                    cue,
+                })
+                .collect(),
+        };
+
+        crossings.sort_by_key(|crossing| (crossing.cycle, crossing.time));
+        if reverse {
+            crossings.reverse();
+        }
+        crossings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names<'a>(crossings: &[Crossing<'a, &'a str>]) -> Vec<&'a str> {
+        crossings
+            .iter()
+            .map(|crossing| match crossing.cue {
+                Cue::Marker(name) => name,
+                Cue::Event(event) => event,
+            })
+            .collect()
+    }
+
+    fn timeline() -> Timeline<&'static str> {
+        Timeline::new()
+            .with_marker("start", Duration::from_secs_f64(0.0))
+            .with_event(Duration::from_secs_f64(1.0), "middle")
+            .with_marker("end", Duration::from_secs_f64(2.0))
+    }
+
+    #[test]
+    fn test_forwards() {
+        let timeline = timeline();
+        assert_eq!(
+            names(&timeline.crossed(Duration::from_secs_f64(0.0), Duration::from_secs_f64(1.0))),
+            ["start"]
+        );
+        assert_eq!(
+            names(&timeline.crossed(Duration::from_secs_f64(1.0), Duration::from_secs_f64(2.5))),
+            ["middle", "end"]
+        );
+        assert!(timeline
+            .crossed(Duration::from_secs_f64(2.5), Duration::from_secs_f64(3.0))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_backwards() {
+        let timeline = timeline();
+        assert_eq!(
+            names(&timeline.crossed(Duration::from_secs_f64(2.0), Duration::from_secs_f64(1.0))),
+            ["end"]
+        );
+        assert_eq!(
+            names(&timeline.crossed(Duration::from_secs_f64(1.5), Duration::from_secs_f64(0.5))),
+            ["middle"]
+        );
+        assert_eq!(
+            names(&timeline.crossed(Duration::from_secs_f64(2.5), Duration::from_secs_f64(0.5))),
+            ["end", "middle"]
+        );
+    }
+
+    #[test]
+    fn test_looping() {
+        let timeline = timeline().with_looping(true);
+        let crossings =
+            timeline.crossed(Duration::from_secs_f64(1.5), Duration::from_secs_f64(4.5));
+        assert_eq!(
+            names(&crossings),
+            ["end", "start", "middle", "end", "start"]
+        );
+        assert_eq!(
+            crossings
+                .iter()
+                .map(|crossing| crossing.cycle)
+                .collect::<Vec<_>>(),
+            [0, 1, 1, 1, 2]
+        );
+
+        // Consecutive frames report each cue exactly once
+        let frames = (0..=45)
+            .map(|i| Duration::from_secs_f64(i as f64 / 10.0))
+            .collect::<Vec<_>>();
+        let total = frames
+            .windows(2)
+            .map(|frame| timeline.crossed(frame[0], frame[1]).len())
+            .sum::<usize>();
+        assert_eq!(
+            total,
+            timeline
+                .crossed(Duration::from_secs_f64(0.0), Duration::from_secs_f64(4.5))
+                .len()
+        );
+    }
+}